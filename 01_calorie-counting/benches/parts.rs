@@ -0,0 +1,16 @@
+use calorie_counting::{parse, part_one, part_two};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn parts(c: &mut Criterion) {
+    aoc::bench::parts(
+        c,
+        "01_calorie-counting",
+        include_str!("../input.txt"),
+        parse,
+        part_one,
+        part_two,
+    );
+}
+
+criterion_group!(benches, parts);
+criterion_main!(benches);
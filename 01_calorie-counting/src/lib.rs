@@ -0,0 +1,31 @@
+//! Day 1: Calorie Counting.
+
+use aoc::Input;
+
+#[derive(PartialEq, PartialOrd, Ord, Eq, Copy, Clone)]
+pub struct Elf(pub i32);
+#[derive(PartialEq, PartialOrd, Ord, Eq, Copy, Clone)]
+pub struct Calories(pub i32);
+
+/// Every elf and the calories they're carrying, most calories first.
+pub fn parse(input: &Input) -> color_eyre::eyre::Result<Vec<(Elf, Calories)>> {
+    let mut elves = Vec::new();
+    for (e, paragraph) in input.paragraphs().enumerate() {
+        let mut c = Calories(0);
+        for line in paragraph.lines() {
+            c.0 += line.parse::<i32>()?;
+        }
+        elves.push((Elf(e as i32), c));
+    }
+
+    elves.sort_by_key(|&(_, calories)| std::cmp::Reverse(calories));
+    Ok(elves)
+}
+
+pub fn part_one(elves: &[(Elf, Calories)]) -> i32 {
+    elves.first().map_or(0, |(_, calories)| calories.0)
+}
+
+pub fn part_two(elves: &[(Elf, Calories)]) -> i32 {
+    elves.iter().take(3).map(|(_, calories)| calories.0).sum()
+}
@@ -0,0 +1,3 @@
+use calorie_counting::{parse, part_one, part_two};
+
+aoc::examples!(parse, part_one, part_two);
@@ -0,0 +1,105 @@
+//! Day 2: Rock Paper Scissors.
+
+use std::str::FromStr;
+
+use aoc::Input;
+use color_eyre::eyre::eyre;
+use log::debug;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Play {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl Play {
+    fn play_value(&self) -> i32 {
+        match self {
+            Play::Rock => 1,
+            Play::Paper => 2,
+            Play::Scissors => 3,
+        }
+    }
+    fn round_value(&self, other: &Self) -> i32 {
+        if self == other {
+            return 3;
+        }
+
+        match (self, other) {
+            (Play::Rock, Play::Paper) => 0,
+            (Play::Paper, Play::Scissors) => 0,
+            (Play::Scissors, Play::Rock) => 0,
+            _ => 6,
+        }
+    }
+
+    pub fn value(&self, other: &Self) -> i32 {
+        self.play_value() + self.round_value(other)
+    }
+
+    /// What to play against `other` when the second column, read as a play
+    /// by `from_str`, is really the cheat.
+    pub fn from_cheat(cheat: &Play, other: &Play) -> Self {
+        match (cheat, other) {
+            // X lose
+            (Play::Rock, Play::Rock) => Play::Scissors,
+            (Play::Rock, Play::Paper) => Play::Rock,
+            (Play::Rock, Play::Scissors) => Play::Paper,
+
+            // Z win
+            (Play::Scissors, Play::Rock) => Play::Paper,
+            (Play::Scissors, Play::Paper) => Play::Scissors,
+            (Play::Scissors, Play::Scissors) => Play::Rock,
+
+            // Y draw
+            (Play::Paper, other) => *other,
+        }
+    }
+}
+
+impl FromStr for Play {
+    type Err = color_eyre::eyre::Report;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "A" | "X" => Ok(Play::Rock),
+            "B" | "Y" => Ok(Play::Paper),
+            "C" | "Z" => Ok(Play::Scissors),
+            _ => Err(eyre!(format!("Found unknown value for Play: '{}'", s))),
+        }
+    }
+}
+
+fn round(me: &Play, them: &Play) -> i32 {
+    debug!("me: {:?} vs {:?} = {}", me, them, me.value(them));
+    me.value(them)
+}
+
+/// Each round's two columns, read as plays.
+pub fn parse(input: &Input) -> color_eyre::eyre::Result<Vec<(Play, Play)>> {
+    let mut rounds = Vec::new();
+    for line in input.lines() {
+        let mut words = line.split_ascii_whitespace();
+        let them =
+            Play::from_str(words.next().unwrap_or_default()).map_err(|e| eyre!(line.error(e)))?;
+        let me = match words.next() {
+            Some(column @ ("X" | "Y" | "Z")) => Play::from_str(column)?,
+            Some(column) => return Err(eyre!(line.error(format!("unknown column '{column}'")))),
+            None => return Err(eyre!(line.error("missing the second column"))),
+        };
+        rounds.push((them, me));
+    }
+    Ok(rounds)
+}
+
+pub fn part_one(rounds: &[(Play, Play)]) -> i32 {
+    rounds.iter().map(|(them, me)| round(me, them)).sum()
+}
+
+pub fn part_two(rounds: &[(Play, Play)]) -> i32 {
+    rounds
+        .iter()
+        .map(|(them, cheat)| round(&Play::from_cheat(cheat, them), them))
+        .sum()
+}
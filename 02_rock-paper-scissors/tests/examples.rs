@@ -0,0 +1,3 @@
+use rock_paper_scissors::{parse, part_one, part_two};
+
+aoc::examples!(parse, part_one, part_two);
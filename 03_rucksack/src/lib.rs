@@ -0,0 +1,94 @@
+//! Day 3: Rucksack Reorganization.
+
+use aoc::{Input, InputError};
+use itertools::Itertools;
+use log::{debug, trace};
+
+fn get_priority(c: char) -> i32 {
+    let priority = if c.is_ascii_lowercase() {
+        (c as i32) - ('a' as i32) + 1
+    } else if c.is_ascii_uppercase() {
+        (c as i32) - ('A' as i32) + 27
+    } else {
+        0
+    };
+    trace!("priority of {} is {}", c, priority);
+    priority
+}
+
+/// Every rucksack, checked to be letters that split into two compartments.
+pub fn parse(input: &Input) -> Result<Vec<String>, InputError> {
+    input
+        .lines()
+        .map(|line| {
+            if !line.chars().all(|c| c.is_ascii_alphabetic()) {
+                Err(line.error("rucksacks can only hold letters"))
+            } else if line.len() % 2 != 0 {
+                Err(line.error("rucksack doesn't split into two compartments"))
+            } else {
+                Ok(line.to_string())
+            }
+        })
+        .collect()
+}
+
+pub fn part_one(rucksacks: &[String]) -> usize {
+    let mut sum = 0;
+    for line in rucksacks {
+        let chars: Vec<char> = line.chars().collect();
+        let (r1, r2) = chars.split_at(chars.len() / 2);
+
+        debug!("Ruck1:  {:?}", r1);
+        debug!("Ruck2:  {:?}", r2);
+        let mut r1_items: [bool; 26 * 2 + 1] = [false; 26 * 2 + 1];
+        let mut r2_items: [bool; 26 * 2 + 1] = [false; 26 * 2 + 1];
+
+        for item in r1.iter() {
+            r1_items[get_priority(*item) as usize] = true;
+        }
+
+        for item in r2.iter() {
+            let i = get_priority(*item) as usize;
+            if !r2_items[i] && r1_items[i] {
+                debug!("Duplicate item is '{}'", item);
+                sum += i;
+            }
+            r2_items[i] = true;
+        }
+    }
+    sum
+}
+
+pub fn part_two(rucksacks: &[String]) -> usize {
+    let mut sum = 0;
+
+    for triplet in &rucksacks.iter().chunks(3) {
+        let rucksacks: Vec<[bool; 53]> = triplet
+            .map(|s| {
+                let mut items: [bool; 26 * 2 + 1] = [false; 26 * 2 + 1];
+                for item in s.chars() {
+                    let i = get_priority(item) as usize;
+                    items[i] = true;
+                }
+
+                items
+            })
+            .collect();
+        let mut badge: [bool; 53] = [true; 53];
+
+        for rucksack in rucksacks {
+            for (i, present) in rucksack.iter().enumerate() {
+                badge[i] &= present;
+            }
+        }
+
+        for (i, present) in badge.iter().enumerate() {
+            if *present {
+                trace!("Badge priority is {}", i);
+                sum += i;
+            }
+        }
+    }
+
+    sum
+}
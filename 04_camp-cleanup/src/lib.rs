@@ -0,0 +1,63 @@
+//! Day 4: Camp Cleanup.
+
+use aoc::{
+    parse::{map, number, pair, tag, terminated, PResult},
+    Input, InputError,
+};
+use log::debug;
+
+#[derive(Debug, Copy, Clone)]
+pub struct Assignment {
+    pub start: i32,
+    pub end: i32,
+}
+
+impl Assignment {
+    pub fn contains(&self, other: &Self) -> bool {
+        self.start <= other.start && self.end >= other.end
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.start <= other.end && self.end >= other.start
+    }
+}
+
+fn assignment<'a>() -> impl Fn(&'a str) -> PResult<'a, Assignment> {
+    map(
+        pair(terminated(number(), tag("-")), number()),
+        |(start, end)| Assignment { start, end },
+    )
+}
+
+/// Each pair of elves' section assignments.
+pub fn parse(input: &Input) -> Result<Vec<(Assignment, Assignment)>, InputError> {
+    input
+        .lines()
+        .map(|line| line.parse_with(pair(terminated(assignment(), tag(",")), assignment())))
+        .collect()
+}
+
+pub fn part_one(assignments: &[(Assignment, Assignment)]) -> usize {
+    let mut contains = 0;
+    for &(a1, a2) in assignments.iter() {
+        if a1.contains(&a2) {
+            debug!("{:?} contains {:?}", a1, a2);
+            contains += 1;
+        } else if a2.contains(&a1) {
+            debug!("{:?} contains {:?}", a2, a1);
+            contains += 1;
+        }
+    }
+    contains
+}
+
+pub fn part_two(assignments: &[(Assignment, Assignment)]) -> usize {
+    let mut overlaps = 0;
+    for &(a1, a2) in assignments.iter() {
+        if a1.overlaps(&a2) {
+            debug!("{:?} overlaps {:?}", a1, a2);
+            overlaps += 1;
+        }
+    }
+    overlaps
+}
@@ -0,0 +1,3 @@
+use camp_cleanup::{parse, part_one, part_two};
+
+aoc::examples!(parse, part_one, part_two);
@@ -0,0 +1,170 @@
+//! Day 5: Supply Stacks.
+
+use aoc::{
+    parse::{alt, char_if, delimited, map, number, opt, pair, preceded, separated, tag, PResult},
+    Input, InputError, Line, Paragraph,
+};
+use log::{debug, log, log_enabled, Level};
+
+/// The drawing of the stacks, and the moves to make with the text of the
+/// line each came from.
+pub struct Supplies {
+    stacks: Vec<Vec<char>>,
+    moves: Vec<(String, Move)>,
+}
+
+fn log_stacks(level: Level, stacks: &[Vec<char>]) {
+    if !log_enabled!(level) {
+        return;
+    }
+    let mut drawing = "Stacks: ".to_owned();
+    for stack in stacks.iter() {
+        if !stack.is_empty() {
+            drawing += &format!("\n{:?}", stack);
+        }
+    }
+    log!(level, "{drawing}");
+}
+
+pub struct Move {
+    count: i32,
+    src: usize,
+    dst: usize,
+}
+
+/// One row of the drawing, with a crate or a gap for each stack.
+fn crate_row<'a>() -> impl Fn(&'a str) -> PResult<'a, Vec<Option<char>>> {
+    let letter = char_if("a crate letter", |c| c.is_ascii_alphabetic());
+    separated(
+        alt((
+            map(delimited(tag("["), letter, tag("]")), Some),
+            map(tag("   "), |_| None),
+        )),
+        tag(" "),
+    )
+}
+
+/// The row of stack numbers under the drawing.
+fn stack_labels<'a>() -> impl Fn(&'a str) -> PResult<'a, Vec<usize>> {
+    separated(delimited(tag(" "), number(), opt(tag(" "))), tag(" "))
+}
+
+fn move_line<'a>() -> impl Fn(&'a str) -> PResult<'a, Move> {
+    map(
+        pair(
+            preceded(tag("move "), number()),
+            pair(
+                preceded(tag(" from "), number()),
+                preceded(tag(" to "), number()),
+            ),
+        ),
+        |(count, (src, dst))| Move { count, src, dst },
+    )
+}
+
+/// The stacks from the drawing, bottom crate first. Stacks are numbered from
+/// 1, so the stack at index 0 is always empty.
+fn parse_stacks(drawing: Paragraph) -> Result<Vec<Vec<char>>, InputError> {
+    let lines: Vec<Line> = drawing.lines().collect();
+    let (labels_line, rows) = lines.split_last().unwrap();
+    let labels = labels_line.parse_with(stack_labels())?;
+    if labels.iter().enumerate().any(|(i, &label)| label != i + 1) {
+        return Err(labels_line.error(format!("stacks should be numbered 1 to {}", labels.len())));
+    }
+
+    let mut stacks = vec![Vec::new(); labels.len() + 1];
+    for line in rows.iter().rev() {
+        let row = line.parse_with(crate_row())?;
+        if row.len() > labels.len() {
+            return Err(line.error(format!("there are only {} stacks", labels.len())));
+        }
+        for (i, value) in row.into_iter().enumerate() {
+            if let Some(value) = value {
+                stacks[i + 1].push(value);
+            }
+        }
+    }
+
+    Ok(stacks)
+}
+
+/// The moves, checked against the stacks they're made on so the parts never
+/// take a crate from an empty stack.
+fn parse_moves(moves: Paragraph, stacks: &[Vec<char>]) -> Result<Vec<(String, Move)>, InputError> {
+    let mut heights: Vec<usize> = stacks.iter().map(Vec::len).collect();
+    moves
+        .lines()
+        .map(|line| {
+            let m = line.parse_with(move_line())?;
+            let n = stacks.len() - 1;
+            if m.src == 0 || m.src > n || m.dst == 0 || m.dst > n {
+                return Err(line.error(format!("there are only {n} stacks")));
+            }
+            if heights[m.src] < m.count as usize {
+                return Err(line.error(format!("stack {} is empty", m.src)));
+            }
+            heights[m.src] -= m.count as usize;
+            heights[m.dst] += m.count as usize;
+            Ok((line.text.to_string(), m))
+        })
+        .collect()
+}
+
+pub fn parse(input: &Input) -> Result<Supplies, InputError> {
+    let mut paragraphs = input.paragraphs();
+    let drawing = paragraphs
+        .next()
+        .ok_or_else(|| input.error(1, "expected a drawing of the stacks"))?;
+    let stacks = parse_stacks(drawing)?;
+    let moves = paragraphs.next().ok_or_else(|| {
+        input.error(
+            drawing.number + drawing.lines().count(),
+            "expected moves after the drawing",
+        )
+    })?;
+    let moves = parse_moves(moves, &stacks)?;
+
+    Ok(Supplies { stacks, moves })
+}
+
+fn tops(stacks: &[Vec<char>]) -> String {
+    stacks.iter().filter_map(|stack| stack.last()).collect()
+}
+
+/// The crates on top after the CrateMover 9000 moves them one at a time.
+pub fn part_one(supplies: &Supplies) -> String {
+    let mut stacks = supplies.stacks.clone();
+    for (_, Move { count, src, dst }) in supplies.moves.iter() {
+        for _ in 0..*count {
+            let c = stacks[*src].pop().expect("moves are checked when parsing");
+            stacks[*dst].push(c);
+        }
+
+        log_stacks(Level::Trace, &stacks);
+    }
+    tops(&stacks)
+}
+
+/// The crates on top after the CrateMover 9001 moves them all at once.
+pub fn part_two(supplies: &Supplies) -> String {
+    let mut stacks = supplies.stacks.clone();
+    log_stacks(Level::Debug, &stacks);
+
+    for (line, Move { count, src, dst }) in supplies.moves.iter() {
+        debug!("> {}", line);
+        debug!("> {} crates from column {} to column {}\n", count, src, dst);
+
+        let mut crane = Vec::new();
+        for _ in 0..*count {
+            crane.push(stacks[*src].pop().expect("moves are checked when parsing"));
+        }
+
+        for _ in 0..*count {
+            let c = crane.pop().unwrap();
+            stacks[*dst].push(c);
+        }
+
+        log_stacks(Level::Debug, &stacks);
+    }
+    tops(&stacks)
+}
@@ -0,0 +1,3 @@
+use supply_stacks::{parse, part_one, part_two};
+
+aoc::examples!(parse, part_one, part_two);
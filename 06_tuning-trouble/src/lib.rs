@@ -0,0 +1,49 @@
+//! Day 6: Tuning Trouble.
+
+use std::collections::VecDeque;
+
+use aoc::Input;
+
+fn are_unique(mut chars: VecDeque<char>) -> bool {
+    let sorted = chars.make_contiguous();
+    sorted.sort();
+
+    for i in 1..sorted.len() {
+        if sorted[i - 1] == sorted[i] {
+            return false;
+        }
+    }
+    true
+}
+
+fn find_start(s: &str, n_unique_chars_required: usize) -> usize {
+    let mut chars = VecDeque::new();
+
+    for (i, c) in s.chars().enumerate() {
+        chars.push_back(c);
+        while chars.len() > n_unique_chars_required {
+            chars.pop_front();
+        }
+
+        if chars.len() == n_unique_chars_required && are_unique(chars.clone()) {
+            return i + 1;
+        }
+    }
+
+    0
+}
+
+/// Every datastream, one per line.
+pub fn parse(input: &Input) -> eyre::Result<Vec<String>> {
+    Ok(input.lines().map(|line| line.to_string()).collect())
+}
+
+/// Where each datastream's start-of-packet marker ends.
+pub fn part_one(datastreams: &[String]) -> Vec<usize> {
+    datastreams.iter().map(|s| find_start(s, 4)).collect()
+}
+
+/// Where each datastream's start-of-message marker ends.
+pub fn part_two(datastreams: &[String]) -> Vec<usize> {
+    datastreams.iter().map(|s| find_start(s, 14)).collect()
+}
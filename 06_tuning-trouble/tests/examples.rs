@@ -0,0 +1,3 @@
+use tuning_trouble::{parse, part_one, part_two};
+
+aoc::examples!(parse, part_one, part_two);
@@ -0,0 +1,261 @@
+//! Day 7: No Space Left On Device.
+
+use aoc::{
+    parse::{alt, map, number, pair, preceded, rest, tag, terminated, word, PResult},
+    Input, InputError,
+};
+use log::{debug, info, trace};
+
+/// A file or directory in a `FileSystem`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct NodeId(usize);
+
+enum Kind {
+    File,
+    Directory(Vec<NodeId>),
+}
+
+struct Node {
+    name: String,
+    parent: Option<NodeId>,
+    /// The file's size, or the total size of everything under the directory.
+    size: i64,
+    kind: Kind,
+}
+
+/// Every file and directory the terminal output has seen, each linking to its
+/// parent. Directory sizes are kept up to date as files are added.
+pub struct FileSystem {
+    nodes: Vec<Node>,
+}
+
+impl Default for FileSystem {
+    fn default() -> Self {
+        FileSystem::new()
+    }
+}
+
+impl FileSystem {
+    pub const ROOT: NodeId = NodeId(0);
+
+    /// A file system with nothing but the root directory.
+    pub fn new() -> FileSystem {
+        FileSystem {
+            nodes: vec![Node {
+                name: "/".to_owned(),
+                parent: None,
+                size: 0,
+                kind: Kind::Directory(Vec::new()),
+            }],
+        }
+    }
+
+    pub fn name(&self, id: NodeId) -> &str {
+        &self.nodes[id.0].name
+    }
+
+    pub fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.nodes[id.0].parent
+    }
+
+    pub fn size(&self, id: NodeId) -> i64 {
+        self.nodes[id.0].size
+    }
+
+    pub fn is_dir(&self, id: NodeId) -> bool {
+        matches!(self.nodes[id.0].kind, Kind::Directory(_))
+    }
+
+    /// What's in a directory, in the order it was listed. Files have nothing.
+    pub fn children(&self, id: NodeId) -> &[NodeId] {
+        match &self.nodes[id.0].kind {
+            Kind::Directory(children) => children,
+            Kind::File => &[],
+        }
+    }
+
+    /// The entry called `name` in `dir`, if there is one.
+    pub fn child(&self, dir: NodeId, name: &str) -> Option<NodeId> {
+        self.children(dir)
+            .iter()
+            .copied()
+            .find(|&child| self.name(child) == name)
+    }
+
+    /// Every directory, the root first.
+    pub fn directories(&self) -> impl Iterator<Item = NodeId> + '_ {
+        (0..self.nodes.len())
+            .map(NodeId)
+            .filter(|&id| self.is_dir(id))
+    }
+
+    /// Where `cd path` from `cwd` ends up. Paths starting with `/` are
+    /// absolute, `..` goes up a level and anything else is a subdirectory.
+    pub fn resolve(&self, cwd: NodeId, path: &str) -> Result<NodeId, String> {
+        let mut dir = if path.starts_with('/') {
+            FileSystem::ROOT
+        } else {
+            cwd
+        };
+        for segment in path.split('/').filter(|s| !s.is_empty() && *s != ".") {
+            dir = match segment {
+                ".." => self.parent(dir).ok_or("already at the root")?,
+                name => match self.child(dir, name) {
+                    Some(child) if self.is_dir(child) => child,
+                    Some(_) => return Err(format!("{name} is a file, not a directory")),
+                    None => return Err(format!("no directory named {name}")),
+                },
+            };
+        }
+        Ok(dir)
+    }
+
+    /// Adds a directory to `dir`, or finds the one already listed there.
+    pub fn add_dir(&mut self, dir: NodeId, name: &str) -> Result<NodeId, String> {
+        match self.child(dir, name) {
+            Some(existing) if self.is_dir(existing) => Ok(existing),
+            Some(_) => Err(format!("{name} was listed as a file before")),
+            None => Ok(self.add(dir, name, 0, Kind::Directory(Vec::new()))),
+        }
+    }
+
+    /// Adds a file to `dir`, or finds the one already listed there, and adds
+    /// its size to every directory above it.
+    pub fn add_file(&mut self, dir: NodeId, name: &str, size: i64) -> Result<NodeId, String> {
+        match self.child(dir, name) {
+            Some(existing) if !self.is_dir(existing) && self.size(existing) == size => Ok(existing),
+            Some(existing) if !self.is_dir(existing) => Err(format!(
+                "{name} was listed with size {} before",
+                self.size(existing)
+            )),
+            Some(_) => Err(format!("{name} was listed as a directory before")),
+            None => {
+                let file = self.add(dir, name, size, Kind::File);
+                let mut ancestor = Some(dir);
+                while let Some(id) = ancestor {
+                    self.nodes[id.0].size += size;
+                    ancestor = self.parent(id);
+                }
+                Ok(file)
+            }
+        }
+    }
+
+    fn add(&mut self, dir: NodeId, name: &str, size: i64, kind: Kind) -> NodeId {
+        let id = NodeId(self.nodes.len());
+        self.nodes.push(Node {
+            name: name.to_owned(),
+            parent: Some(dir),
+            size,
+            kind,
+        });
+        if let Kind::Directory(children) = &mut self.nodes[dir.0].kind {
+            children.push(id);
+        }
+        id
+    }
+}
+
+fn log_structure(fs: &FileSystem, id: NodeId, leading: String) {
+    if fs.is_dir(id) {
+        debug!("{}- {} (dir)", leading, fs.name(id));
+        for &child in fs.children(id) {
+            log_structure(fs, child, leading.clone() + "  ");
+        }
+    } else {
+        debug!("{}- {} (file, size={})", leading, fs.name(id), fs.size(id));
+    }
+}
+
+fn log_sizes(fs: &FileSystem, id: NodeId, leading: String) {
+    for &child in fs.children(id) {
+        log_sizes(fs, child, leading.clone() + "  ");
+    }
+    if fs.is_dir(id) {
+        debug!("{} {} - {}", leading, fs.name(id), fs.size(id));
+    }
+}
+
+/// One line of the terminal output.
+enum Terminal<'a> {
+    Cd(&'a str),
+    Ls,
+    Unknown(&'a str),
+    Dir(&'a str),
+    File(&'a str, i64),
+}
+
+fn terminal<'a>() -> impl Fn(&'a str) -> PResult<'a, Terminal<'a>> {
+    alt((
+        map(preceded(tag("$ cd "), word()), Terminal::Cd),
+        map(tag("$ ls"), |_| Terminal::Ls),
+        map(preceded(tag("$ "), rest()), Terminal::Unknown),
+        map(preceded(tag("dir "), word()), Terminal::Dir),
+        map(
+            pair(terminated(number(), tag(" ")), word()),
+            |(size, name)| Terminal::File(name, size),
+        ),
+    ))
+}
+
+/// The file system the terminal output explores. Listings have to follow an
+/// `ls`, and can't name something different to what was listed before.
+pub fn parse(input: &Input) -> Result<FileSystem, InputError> {
+    let mut fs = FileSystem::new();
+    let mut cwd = FileSystem::ROOT;
+    let mut listing = false;
+
+    for line in input.lines() {
+        trace!("{}\ncwd:  {}", &*line, fs.name(cwd));
+
+        match line.parse_with(terminal())? {
+            Terminal::Cd(path) => {
+                cwd = fs.resolve(cwd, path).map_err(|e| line.error(e))?;
+                listing = false;
+            }
+            Terminal::Ls => listing = true,
+            Terminal::Unknown(command) => {
+                return Err(line.error(format!("unknown command '{command}'")));
+            }
+            Terminal::Dir(_) | Terminal::File(_, _) if !listing => {
+                return Err(line.error("output without an ls before it"));
+            }
+            Terminal::Dir(name) | Terminal::File(name, _) if name.contains('/') || name == ".." => {
+                return Err(line.error(format!("'{name}' isn't a file name")));
+            }
+            Terminal::Dir(name) => {
+                fs.add_dir(cwd, name).map_err(|e| line.error(e))?;
+            }
+            Terminal::File(_, size) if size < 0 => {
+                return Err(line.error("file sizes can't be negative"));
+            }
+            Terminal::File(name, size) => {
+                fs.add_file(cwd, name, size).map_err(|e| line.error(e))?;
+            }
+        }
+    }
+
+    Ok(fs)
+}
+
+/// The total size of the directories of at most 100000.
+pub fn part_one(fs: &FileSystem) -> i64 {
+    log_structure(fs, FileSystem::ROOT, "".to_owned());
+    debug!("-------------------------------------");
+    log_sizes(fs, FileSystem::ROOT, "".to_owned());
+    fs.directories()
+        .filter(|&dir| dir != FileSystem::ROOT)
+        .map(|dir| fs.size(dir))
+        .filter(|&size| size <= 100000)
+        .sum()
+}
+
+/// The size of the smallest directory that frees up enough space.
+pub fn part_two(fs: &FileSystem) -> Option<i64> {
+    let root_size = fs.size(FileSystem::ROOT);
+    info!("root size is {}", root_size);
+    fs.directories()
+        .map(|dir| fs.size(dir))
+        .filter(|dir_size| 70000000 - (root_size - dir_size) > 30000000)
+        .min()
+}
@@ -0,0 +1,39 @@
+use aoc::{Input, InputError};
+use no_space::{parse, part_one, part_two};
+
+aoc::examples!(parse, part_one, part_two);
+
+fn parse_error(text: &str) -> InputError {
+    match parse(&Input::new("bad.txt", text)) {
+        Ok(_) => panic!("{text:?} parsed"),
+        Err(e) => e,
+    }
+}
+
+#[test]
+fn cd_into_a_missing_directory() {
+    let e = parse_error("$ cd /\n$ ls\ndir a\n$ cd b\n");
+    assert_eq!(e.line, 4);
+    assert_eq!(e.message, "no directory named b");
+}
+
+#[test]
+fn cd_up_from_the_root() {
+    let e = parse_error("$ cd /\n$ cd ..\n");
+    assert_eq!(e.line, 2);
+    assert_eq!(e.message, "already at the root");
+}
+
+#[test]
+fn unknown_command() {
+    let e = parse_error("$ cd /\n$ rm -rf a\n");
+    assert_eq!(e.line, 2);
+    assert_eq!(e.message, "unknown command 'rm -rf a'");
+}
+
+#[test]
+fn output_without_an_ls() {
+    let e = parse_error("$ cd /\n14848514 b.txt\n");
+    assert_eq!(e.line, 2);
+    assert_eq!(e.message, "output without an ls before it");
+}
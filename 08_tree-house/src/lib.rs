@@ -0,0 +1,183 @@
+//! Day 8: Treetop Tree House.
+
+use aoc::{Input, InputError};
+use log::{debug, log_enabled, trace, Level};
+
+/// Every tree's height, and whether it's been seen yet.
+pub type Forest = Vec<Vec<(i32, bool)>>;
+
+/// How many trees can be seen from outside the grid.
+#[allow(clippy::needless_range_loop)]
+pub fn part_one(forest: &Forest) -> usize {
+    let mut forest = forest.clone();
+    let h = forest.len();
+    let w = forest[0].len();
+
+    for row in 0..h {
+        let mut max_height = -1;
+        for col in 0..w {
+            if forest[row][col].0 > max_height {
+                forest[row][col].1 = true;
+                max_height = forest[row][col].0;
+            }
+        }
+
+        let mut max_height = -1;
+        for col in (0..w).rev() {
+            if forest[row][col].0 > max_height {
+                forest[row][col].1 = true;
+                max_height = forest[row][col].0;
+            }
+        }
+    }
+
+    for col in 0..w {
+        let mut max_height = -1;
+        for row in 0..h {
+            if forest[row][col].0 > max_height {
+                forest[row][col].1 = true;
+                max_height = forest[row][col].0;
+            }
+        }
+
+        let mut max_height = -1;
+        for row in (0..h).rev() {
+            if forest[row][col].0 > max_height {
+                forest[row][col].1 = true;
+                max_height = forest[row][col].0;
+            }
+        }
+    }
+
+    if log_enabled!(Level::Debug) {
+        let drawing = forest.iter().map(|grove| {
+            let trees: String = grove
+                .iter()
+                .map(|tree| if tree.1 { 'X' } else { '.' })
+                .collect();
+            format!("  {trees}")
+        });
+        debug!("{}", drawing.collect::<Vec<_>>().join("\n"));
+    }
+
+    forest.iter().flatten().filter(|tree| tree.1).count()
+}
+
+fn get_scenic_score(mut forest: Forest, tree_row: usize, tree_col: usize, show: bool) -> usize {
+    let h = forest.len();
+    let w = forest[0].len();
+
+    let tree_height = forest[tree_row][tree_col].0;
+
+    let mut visible_left = 0;
+    for (i, c) in (0..tree_col).rev().enumerate() {
+        visible_left = i + 1;
+        forest[tree_row][c].1 = true;
+        if forest[tree_row][c].0 >= tree_height {
+            break;
+        }
+    }
+
+    let mut visible_right = 0;
+    for (i, c) in ((tree_col + 1)..w).enumerate() {
+        visible_right = i + 1;
+        forest[tree_row][c].1 = true;
+        if forest[tree_row][c].0 >= tree_height {
+            break;
+        }
+    }
+
+    let mut visible_up = 0;
+    for (i, r) in (0..tree_row).rev().enumerate() {
+        visible_up = i + 1;
+        forest[r][tree_col].1 = true;
+        if forest[r][tree_col].0 >= tree_height {
+            break;
+        }
+    }
+
+    let mut visible_down = 0;
+    for (i, r) in ((tree_row + 1)..h).enumerate() {
+        visible_down = i + 1;
+        forest[r][tree_col].1 = true;
+        if forest[r][tree_col].0 >= tree_height {
+            break;
+        }
+    }
+
+    let score = visible_left * visible_right * visible_up * visible_down;
+
+    if show && log_enabled!(Level::Debug) {
+        let mut drawing = String::new();
+        for (r, grove) in forest.iter().enumerate() {
+            drawing += "  ";
+            for (c, tree) in grove.iter().enumerate() {
+                if (r == tree_row && c == tree_col) || tree.1 {
+                    drawing += &tree.0.to_string();
+                } else {
+                    drawing.push('.');
+                }
+            }
+            drawing.push('\n');
+        }
+
+        debug!("{drawing}Scenic score: {}\n", score);
+    }
+
+    score
+}
+
+/// The highest scenic score, and the column and row of the tree with it.
+pub fn best_tree(forest: &Forest) -> (usize, (usize, usize)) {
+    let h = forest.len();
+    let w = forest[0].len();
+
+    let mut position = (0, 0);
+    let mut score = 0;
+    for row in 0..h {
+        for col in 0..w {
+            let new_score = get_scenic_score(forest.clone(), row, col, false);
+            if new_score > score {
+                position = (col, row);
+                score = new_score;
+            }
+        }
+    }
+
+    get_scenic_score(forest.clone(), position.1, position.0, true);
+
+    (score, position)
+}
+
+pub fn part_two(forest: &Forest) -> usize {
+    best_tree(forest).0
+}
+
+pub fn parse(input: &Input) -> Result<Forest, InputError> {
+    let mut forest: Forest = Vec::new();
+
+    for line in input.lines() {
+        let mut grove = Vec::new();
+        for c in line.chars() {
+            let height = c
+                .to_digit(10)
+                .ok_or_else(|| line.error(format!("'{c}' isn't a tree height")))?;
+            grove.push((height as i32, false));
+        }
+        forest.push(grove);
+    }
+
+    if forest.is_empty() || forest.iter().any(|grove| grove.len() != forest[0].len()) {
+        return Err(input.error(1, "the forest should be a rectangle of trees"));
+    }
+
+    if log_enabled!(Level::Trace) {
+        let heights = forest.iter().map(|grove| {
+            let trees: String = grove.iter().map(|tree| tree.0.to_string()).collect();
+            format!("  {trees}")
+        });
+        trace!("{}", heights.collect::<Vec<_>>().join("\n"));
+    }
+
+    Ok(forest)
+}
@@ -0,0 +1,3 @@
+use tree_house::{parse, part_one, part_two};
+
+aoc::examples!(parse, part_one, part_two);
@@ -0,0 +1,176 @@
+//! Day 9: Rope Bridge.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use aoc::{Input, InputError};
+use log::{debug, log_enabled, trace, warn, Level};
+
+#[derive(Copy, Clone)]
+struct Position {
+    x: i32,
+    y: i32,
+}
+
+impl Position {
+    fn wiggle(&mut self, direction: &str) {
+        match direction {
+            "R" => self.x += 1,
+            "L" => self.x -= 1,
+            "U" => self.y += 1,
+            "D" => self.y -= 1,
+            _ => warn!("Unknown direction: '{}'", direction),
+        }
+    }
+
+    fn chase(&mut self, head: Self) {
+        let x_d = head.x - self.x;
+        let y_d = head.y - self.y;
+
+        if x_d.abs() < 2 && y_d.abs() < 2 {
+            return;
+        }
+
+        if self.x != head.x && self.y != head.y {
+            if x_d.is_positive() {
+                self.x += 1;
+            } else {
+                self.x -= 1;
+            }
+
+            if y_d.is_positive() {
+                self.y += 1;
+            } else {
+                self.y -= 1;
+            }
+        } else if self.x == head.x {
+            if y_d.is_positive() {
+                self.y += 1;
+            } else {
+                self.y -= 1;
+            }
+        } else if self.y == head.y {
+            if x_d.is_positive() {
+                self.x += 1;
+            } else {
+                self.x -= 1;
+            }
+        }
+    }
+}
+
+fn show_rope(head: Position, tail: Position, x0: i32, x1: i32, y0: i32, y1: i32) {
+    if !log_enabled!(Level::Trace) {
+        return;
+    }
+    let h = (head.x, head.y);
+    let t = (tail.x, tail.y);
+    let s = (0, 0);
+    let mut drawing = String::new();
+    for y in (y0..y1).rev() {
+        drawing += "  ";
+        for x in x0..x1 {
+            if (x, y) == h {
+                drawing.push('H');
+            } else if (x, y) == t {
+                drawing.push('T');
+            } else if (x, y) == s {
+                drawing.push('s');
+            } else {
+                drawing.push('.');
+            }
+        }
+        drawing.push('\n');
+    }
+    trace!("{drawing}");
+}
+
+/// Each movement of the head: a direction and how many steps to take.
+pub fn parse(input: &Input) -> Result<Vec<(String, i32)>, InputError> {
+    input
+        .lines()
+        .map(|line| {
+            let mut words = line.split_ascii_whitespace();
+            let direction = match words.next() {
+                Some(direction @ ("R" | "L" | "U" | "D")) => direction,
+                _ => return Err(line.error("expected a direction of R, L, U or D")),
+            };
+            let steps = words
+                .next()
+                .and_then(|steps| steps.parse::<i32>().ok())
+                .ok_or_else(|| line.error("expected a number of steps"))?;
+            Ok((direction.to_string(), steps))
+        })
+        .collect()
+}
+
+/// Where the tail of a two knot rope goes.
+pub fn part_one(movements: &[(String, i32)]) -> usize {
+    let mut head = Position { x: 0, y: 0 };
+    let mut tail = Position { x: 0, y: 0 };
+
+    let mut positions: BTreeMap<i32, BTreeSet<i32>> = BTreeMap::new();
+
+    for (direction, steps) in movements {
+        debug!("\n== {} {} ==\n", direction, steps);
+
+        for _ in 0..*steps {
+            head.wiggle(direction);
+            tail.chase(head);
+            positions
+                .entry(tail.x)
+                .and_modify(|y_positions| {
+                    y_positions.insert(tail.y);
+                })
+                .or_insert_with(|| {
+                    let mut y_positions = BTreeSet::new();
+                    y_positions.insert(tail.y);
+                    y_positions
+                });
+            show_rope(head, tail, 0, 6, 0, 5);
+        }
+    }
+
+    let unique_positions = positions
+        .iter()
+        .fold(0, |count, (_x_position, y_positions)| {
+            count + y_positions.len()
+        });
+    unique_positions
+}
+
+/// Where the tail of a ten knot rope goes.
+pub fn part_two(movements: &[(String, i32)]) -> usize {
+    let mut rope: [Position; 10] = [Position { x: 0, y: 0 }; 10];
+
+    let mut positions: BTreeMap<i32, BTreeSet<i32>> = BTreeMap::new();
+
+    for (direction, steps) in movements {
+        debug!("\n== {} {} ==\n", direction, steps);
+
+        for _ in 0..*steps {
+            rope[0].wiggle(direction);
+            for i in 1..rope.len() {
+                rope[i].chase(rope[i - 1]);
+            }
+
+            positions
+                .entry(rope.last().unwrap().x)
+                .and_modify(|y_positions| {
+                    y_positions.insert(rope.last().unwrap().y);
+                })
+                .or_insert_with(|| {
+                    let mut y_positions = BTreeSet::new();
+                    y_positions.insert(rope.last().unwrap().y);
+                    y_positions
+                });
+        }
+        show_rope(rope[0], *rope.last().unwrap(), -11, 15, -5, 16);
+    }
+
+    let unique_positions = positions
+        .iter()
+        .fold(0, |count, (_x_position, y_positions)| {
+            count + y_positions.len()
+        });
+    unique_positions
+}
@@ -0,0 +1,3 @@
+use rope_bridge::{parse, part_one, part_two};
+
+aoc::examples!(parse, part_one, part_two);
@@ -0,0 +1,20 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use monkey_in_the_middle::{parse, part_one, part_two};
+
+fn parts(c: &mut Criterion) {
+    aoc::bench::parts(
+        c,
+        "11_monkey-in-the-middle",
+        include_str!("../input.txt"),
+        parse,
+        part_one,
+        part_two,
+    );
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = parts
+}
+criterion_main!(benches);
@@ -0,0 +1,275 @@
+//! Day 11: Monkey in the Middle.
+
+use aoc::{
+    parse::{alt, char_if, delimited, map, number, pair, preceded, separated, tag, PResult},
+    Input, InputError, Paragraph,
+};
+use eyre::Result;
+use log::{debug, log_enabled, Level};
+
+#[derive(Debug, Clone)]
+struct Item(i64);
+
+#[derive(Debug, Clone, Copy)]
+enum Operand {
+    Old,
+    Value(i64),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Operation {
+    lhs: Operand,
+    op: Op,
+    rhs: Operand,
+}
+
+impl Operation {
+    fn apply(&self, old: i64) -> i64 {
+        let value = |operand| match operand {
+            Operand::Old => old,
+            Operand::Value(value) => value,
+        };
+        let (lhs, rhs) = (value(self.lhs), value(self.rhs));
+        match self.op {
+            Op::Add => lhs + rhs,
+            Op::Sub => lhs - rhs,
+            Op::Mul => lhs * rhs,
+            Op::Div => lhs / rhs,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Monkey {
+    items: Vec<Item>,
+    operation: Operation,
+    divisible_by: i64,
+    lcd: i64,
+    dst_true: usize,
+    dst_false: usize,
+    inspections: i32,
+}
+
+impl Monkey {
+    fn inspect(&mut self, item: Item) -> (usize, Item) {
+        // Do operation
+        let mut worry_level = self.operation.apply(item.0);
+
+        // Divide by 3, rounding towards zero
+        worry_level /= 3;
+
+        self.inspections += 1;
+
+        if worry_level % self.divisible_by == 0 {
+            (self.dst_true, Item(worry_level))
+        } else {
+            (self.dst_false, Item(worry_level))
+        }
+    }
+
+    fn inspect2(&mut self, item: Item) -> (usize, Item) {
+        // Do operation
+        let mut worry_level = self.operation.apply(item.0);
+
+        worry_level %= self.lcd;
+
+        self.inspections += 1;
+
+        if worry_level % self.divisible_by == 0 {
+            (self.dst_true, Item(worry_level))
+        } else {
+            (self.dst_false, Item(worry_level))
+        }
+    }
+}
+
+fn operand<'a>() -> impl Fn(&'a str) -> PResult<'a, Operand> {
+    alt((
+        map(tag("old"), |_| Operand::Old),
+        map(number(), Operand::Value),
+    ))
+}
+
+fn operation<'a>() -> impl Fn(&'a str) -> PResult<'a, Operation> {
+    let op = delimited(
+        tag(" "),
+        map(
+            char_if("an operator", |c| "+-*/".contains(c)),
+            |c| match c {
+                '+' => Op::Add,
+                '-' => Op::Sub,
+                '*' => Op::Mul,
+                _ => Op::Div,
+            },
+        ),
+        tag(" "),
+    );
+    map(
+        preceded(
+            tag("  Operation: new = "),
+            pair(operand(), pair(op, operand())),
+        ),
+        |(lhs, (op, rhs))| Operation { lhs, op, rhs },
+    )
+}
+
+fn parse_monkey(index: usize, paragraph: Paragraph) -> Result<Monkey, InputError> {
+    let mut lines = paragraph.lines();
+    let mut line = || {
+        lines
+            .next()
+            .ok_or_else(|| paragraph.error(format!("monkey {index} is missing lines")))
+    };
+
+    let header = line()?;
+    let id = header.parse_with(delimited(tag("Monkey "), number::<usize>(), tag(":")))?;
+    if id != index {
+        return Err(header.error(format!("expected monkey {index}")));
+    }
+
+    let items = line()?.parse_with(preceded(
+        tag("  Starting items: "),
+        separated(map(number(), Item), tag(", ")),
+    ))?;
+    let operation = line()?.parse_with(operation())?;
+
+    let test = line()?;
+    let divisible_by = test.parse_with(preceded(tag("  Test: divisible by "), number()))?;
+    if divisible_by <= 0 {
+        return Err(test.error("can only test divisibility by positive numbers"));
+    }
+
+    let dst_true = line()?.parse_with(preceded(tag("    If true: throw to monkey "), number()))?;
+    let dst_false =
+        line()?.parse_with(preceded(tag("    If false: throw to monkey "), number()))?;
+
+    if let Ok(extra) = line() {
+        return Err(extra.error(format!("unexpected line after monkey {index}")));
+    }
+
+    Ok(Monkey {
+        items,
+        operation,
+        divisible_by,
+        lcd: 0,
+        dst_true,
+        dst_false,
+        inspections: 0,
+    })
+}
+
+/// The monkeys, ready to throw items with the least common multiple of
+/// their tests worked out.
+pub fn parse(input: &Input) -> Result<Vec<Monkey>, InputError> {
+    let paragraphs: Vec<Paragraph> = input.paragraphs().collect();
+    let mut monkeys = paragraphs
+        .iter()
+        .enumerate()
+        .map(|(index, paragraph)| parse_monkey(index, *paragraph))
+        .collect::<Result<Vec<Monkey>, InputError>>()?;
+
+    for (monkey, paragraph) in monkeys.iter().zip(paragraphs.iter()) {
+        // The targets are on the last two lines of each monkey.
+        for (dst, line) in [monkey.dst_true, monkey.dst_false]
+            .into_iter()
+            .zip(paragraph.lines().skip(4))
+        {
+            if dst >= monkeys.len() {
+                return Err(line.error(format!(
+                    "there are only {} monkeys to throw to",
+                    monkeys.len()
+                )));
+            }
+        }
+    }
+
+    let lcd = monkeys
+        .iter()
+        .fold(1, |lcd, monkey| lcd * monkey.divisible_by);
+    for monkey in monkeys.iter_mut() {
+        monkey.lcd = lcd;
+    }
+
+    Ok(monkeys)
+}
+
+pub fn part_one(monkeys: &[Monkey]) -> i64 {
+    let mut monkeys = monkeys.to_vec();
+    for round in 1..21 {
+        for i in 0..monkeys.len() {
+            let items = monkeys[i].items.clone();
+            monkeys[i].items.clear();
+            for item in items {
+                let (dst, item) = monkeys[i].inspect(item);
+                monkeys[dst].items.push(item);
+            }
+        }
+
+        if log_enabled!(Level::Debug) {
+            let mut holding = format!(
+                "After round {}, the monkeys are holding items with these worry levels:",
+                round
+            );
+            for (i, monkey) in monkeys.iter().enumerate() {
+                holding += &format!("\nMonkey {}: ", i);
+                for item in monkey.items.iter() {
+                    holding += &format!("{}, ", item.0);
+                }
+            }
+            debug!("{holding}\n");
+        }
+    }
+
+    let mut inspections = Vec::new();
+    for (i, monkey) in monkeys.iter().enumerate() {
+        debug!("Monkey {} inspected items {} times.", i, monkey.inspections);
+        inspections.push(monkey.inspections);
+    }
+
+    debug!("Inspections: {:?}", inspections);
+    inspections.sort();
+    inspections.reverse();
+    inspections[0] as i64 * inspections[1] as i64
+}
+
+pub fn part_two(monkeys: &[Monkey]) -> i64 {
+    let mut monkeys = monkeys.to_vec();
+    for round in 1..10001 {
+        for i in 0..monkeys.len() {
+            let items = monkeys[i].items.clone();
+            monkeys[i].items.clear();
+            for item in items {
+                let (dst, item) = monkeys[i].inspect2(item);
+                monkeys[dst].items.push(item);
+            }
+        }
+
+        if round == 1 || round == 20 || round % 50 == 0 {
+            debug!("\n== After round {} ==", round);
+            for (i, monkey) in monkeys.iter().enumerate() {
+                debug!("Monkey {} inspected items {} times.", i, monkey.inspections);
+            }
+        }
+    }
+
+    let mut inspections = Vec::new();
+    for (i, monkey) in monkeys.iter().enumerate() {
+        debug!("Monkey {} inspected items {} times.", i, monkey.inspections);
+        inspections.push(monkey.inspections);
+    }
+
+    debug!("Inspections: {:?}", inspections);
+    inspections.sort();
+    inspections.reverse();
+    let i0 = inspections[0] as i64;
+    let i1 = inspections[1] as i64;
+    i0 * i1
+}
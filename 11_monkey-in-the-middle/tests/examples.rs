@@ -0,0 +1,3 @@
+use monkey_in_the_middle::{parse, part_one, part_two};
+
+aoc::examples!(parse, part_one, part_two);
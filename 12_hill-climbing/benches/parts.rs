@@ -0,0 +1,20 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use hill_climbing::{parse, part_one, part_two};
+
+fn parts(c: &mut Criterion) {
+    aoc::bench::parts(
+        c,
+        "12_hill-climbing",
+        include_str!("../input.txt"),
+        parse,
+        part_one,
+        part_two,
+    );
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = parts
+}
+criterion_main!(benches);
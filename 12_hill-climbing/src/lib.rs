@@ -0,0 +1,199 @@
+//! Day 12: Hill Climbing Algorithm.
+
+use std::collections::BTreeSet;
+
+use aoc::{Input, InputError};
+use log::{debug, log_enabled, trace, Level};
+
+#[derive(Copy, Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+struct Height(i32);
+#[derive(Copy, Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+struct StepCount(i32);
+#[derive(Copy, Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Position {
+    row: usize,
+    col: usize,
+}
+
+impl Position {
+    fn new(row: usize, col: usize) -> Self {
+        Self { row, col }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+struct Spot {
+    height: Height,
+    step_count: StepCount,
+    position: Position,
+}
+
+impl Spot {
+    fn can_reach(&self, other: &Self) -> bool {
+        other.height.0 <= self.height.0 + 1 && other.step_count.0 > self.step_count.0
+    }
+}
+
+type Map = Vec<Vec<Spot>>;
+
+/// The heightmap, with where we start and where the best signal is.
+pub struct Hill {
+    map: Map,
+    start: Spot,
+    end: Spot,
+}
+
+fn list_reachable(map: &Map, current_pos: Position) -> BTreeSet<Position> {
+    let mut reachable = BTreeSet::new();
+    let r = current_pos.row;
+    let c = current_pos.col;
+
+    if r > 0 && map[r][c].can_reach(&map[r - 1][c]) {
+        reachable.insert(map[r - 1][c].position);
+    }
+    if r < (map.len() - 1) && map[r][c].can_reach(&map[r + 1][c]) {
+        reachable.insert(map[r + 1][c].position);
+    }
+    if c > 0 && map[r][c].can_reach(&map[r][c - 1]) {
+        reachable.insert(map[r][c - 1].position);
+    }
+    if c < (map[r].len() - 1) && map[r][c].can_reach(&map[r][c + 1]) {
+        reachable.insert(map[r][c + 1].position);
+    }
+
+    reachable
+}
+
+pub fn parse(input: &Input) -> Result<Hill, InputError> {
+    let mut map = Vec::new();
+    let mut start = None;
+    let mut end = None;
+    for (row_i, line) in input.lines().enumerate() {
+        let mut row = Vec::new();
+        for (col_i, c) in line.chars().enumerate() {
+            let height = Height(match c {
+                'S' => 0,
+                'E' => 25,
+                'a'..='z' => c as i32 - 'a' as i32,
+                c => return Err(line.error(format!("'{c}' isn't a height"))),
+            });
+            let step_count = StepCount(i32::MAX);
+            let position = Position::new(row_i, col_i);
+            let spot = Spot {
+                height,
+                step_count,
+                position,
+            };
+            if c == 'S' {
+                start = Some(spot);
+            } else if c == 'E' {
+                end = Some(spot);
+            }
+            row.push(spot);
+        }
+        map.push(row);
+    }
+
+    if map.is_empty() || map.iter().any(|row| row.len() != map[0].len()) {
+        return Err(input.error(1, "the heightmap should be a rectangle"));
+    }
+    let start = start.ok_or_else(|| input.error(1, "there's no start marked with S"))?;
+    let end = end.ok_or_else(|| input.error(1, "there's no best signal marked with E"))?;
+
+    Ok(Hill { map, start, end })
+}
+
+pub fn log_map(hill: &Hill) {
+    if !log_enabled!(Level::Debug) {
+        return;
+    }
+    let mut drawing = String::new();
+    for row in hill.map.iter() {
+        for spot in row {
+            drawing += &format!("{:2}", spot.height.0);
+        }
+        drawing.push('\n');
+    }
+    debug!("{drawing}");
+}
+
+fn log_map_steps(map: &Map) {
+    if !log_enabled!(Level::Trace) {
+        return;
+    }
+    let mut drawing = String::new();
+    for row in map {
+        for spot in row {
+            if spot.step_count.0 != i32::MAX {
+                drawing += &format!("{:3} ", spot.step_count.0);
+            } else {
+                drawing += " XX ";
+            }
+        }
+        drawing.push('\n');
+    }
+    trace!("{drawing}");
+}
+
+fn dijkstra(mut map: Map, s: Spot, e: Spot) -> Option<StepCount> {
+    let mut step_count = StepCount(0);
+    let mut reachable = BTreeSet::new();
+    reachable.insert(s.position);
+
+    loop {
+        if reachable.is_empty() {
+            return None;
+        }
+        let mut next_reachable = BTreeSet::new();
+        for current_pos in reachable.iter() {
+            map[current_pos.row][current_pos.col].step_count = step_count;
+
+            trace!("Current spot: {:?}", current_pos);
+            if *current_pos == e.position {
+                return Some(step_count);
+            }
+            next_reachable.append(&mut list_reachable(&map, *current_pos));
+        }
+        trace!(
+            "{} next spots after {} steps",
+            reachable.len(),
+            step_count.0
+        );
+        step_count.0 += 1;
+        reachable = next_reachable;
+
+        log_map_steps(&map);
+    }
+}
+
+/// The fewest steps from the start to the best signal.
+pub fn part_one(hill: &Hill) -> Option<i32> {
+    dijkstra(hill.map.clone(), hill.start, hill.end).map(|step_count| step_count.0)
+}
+
+/// The fewest steps from any lowest square to the best signal, and which
+/// square that is.
+pub fn best_start(hill: &Hill) -> (i32, Position) {
+    let mut shortest_step_count = StepCount(i32::MAX);
+    let mut shortest_position = hill.start.position;
+    for row in hill.map.iter() {
+        for spot in row {
+            if spot.height.0 == 0 {
+                let step_count = dijkstra(hill.map.clone(), *spot, hill.end);
+                trace!("Takes {:?} steps from {:?}", step_count, spot.position);
+                if let Some(step_count) = step_count {
+                    if step_count < shortest_step_count {
+                        shortest_step_count = step_count;
+                        shortest_position = spot.position;
+                    }
+                }
+            }
+        }
+    }
+
+    (shortest_step_count.0, shortest_position)
+}
+
+pub fn part_two(hill: &Hill) -> i32 {
+    best_start(hill).0
+}
@@ -0,0 +1,3 @@
+use hill_climbing::{parse, part_one, part_two};
+
+aoc::examples!(parse, part_one, part_two);
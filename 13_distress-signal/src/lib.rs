@@ -0,0 +1,127 @@
+//! Day 13: Distress Signal.
+
+use aoc::{
+    parse::{alt, delimited, map, number, separated, tag, PResult},
+    Input, InputError,
+};
+use log::trace;
+
+#[derive(Clone, Debug)]
+pub enum Packet {
+    Integer(i32),
+    List(Vec<Packet>),
+}
+
+impl Eq for Packet {}
+
+impl PartialEq for Packet {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Integer(l0), Self::Integer(r0)) => l0 == r0,
+            (Self::Integer(l0), Self::List(r0)) => {
+                let l1 = vec![Packet::Integer(*l0)];
+                &l1 == r0
+            }
+            (Self::List(l0), Self::Integer(r0)) => {
+                let r1 = vec![Packet::Integer(*r0)];
+                l0 == &r1
+            }
+            (Self::List(l0), Self::List(r0)) => l0 == r0,
+        }
+    }
+}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        match (self, other) {
+            (Self::Integer(l0), Self::Integer(r0)) => l0.cmp(r0),
+            (Self::Integer(l0), Self::List(r0)) => {
+                let l1 = vec![Packet::Integer(*l0)];
+                l1.cmp(r0)
+            }
+            (Self::List(l0), Self::Integer(r0)) => {
+                let r1 = vec![Packet::Integer(*r0)];
+                l0.cmp(&r1)
+            }
+            (Self::List(l0), Self::List(r0)) => l0.cmp(r0),
+        }
+    }
+}
+
+/// A bracketed, comma separated list of packets.
+fn list(input: &str) -> PResult<'_, Packet> {
+    alt((
+        map(
+            delimited(tag("["), separated(packet, tag(",")), tag("]")),
+            Packet::List,
+        ),
+        map(tag("[]"), |_| Packet::List(Vec::new())),
+    ))(input)
+}
+
+fn packet(input: &str) -> PResult<'_, Packet> {
+    alt((map(number(), Packet::Integer), list))(input)
+}
+
+/// Every packet, in the order they were received.
+pub fn parse(input: &Input) -> Result<Vec<Packet>, InputError> {
+    input
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| line.parse_with(list))
+        .collect()
+}
+
+/// The sum of the indices of the pairs that are in the right order.
+pub fn part_one(packets: &[Packet]) -> usize {
+    let mut ordered_indices_sum = 0;
+
+    let mut packet_pairs = Vec::new();
+    for i in (1..packets.len()).step_by(2) {
+        packet_pairs.push((packets[i - 1].clone(), packets[i].clone()));
+    }
+
+    for (index, packet_pair) in packet_pairs.iter().enumerate() {
+        trace!("== Pair {} ==", index + 1);
+        trace!("{:?}", packet_pair.0);
+        trace!("{:?}", packet_pair.1);
+        if packet_pair.0 < packet_pair.1 {
+            ordered_indices_sum += index + 1;
+        }
+    }
+    ordered_indices_sum
+}
+
+/// Where the two divider packets end up once every packet is sorted.
+pub fn divider_indices(packets: &[Packet]) -> (usize, usize) {
+    let mut packets = packets.to_vec();
+    let divider = |n| Packet::List(vec![Packet::List(vec![Packet::Integer(n)])]);
+    let dividers = [divider(2), divider(6)];
+    packets.extend(dividers.clone());
+
+    let mut index2 = 0;
+    let mut index6 = 0;
+
+    packets.sort();
+
+    for (index, packet) in packets.iter().enumerate() {
+        if *packet == dividers[0] {
+            index2 = index + 1;
+        } else if *packet == dividers[1] {
+            index6 = index + 1;
+        }
+    }
+    (index2, index6)
+}
+
+/// The decoder key, from where the divider packets end up.
+pub fn part_two(packets: &[Packet]) -> usize {
+    let (index2, index6) = divider_indices(packets);
+    index2 * index6
+}
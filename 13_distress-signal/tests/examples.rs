@@ -0,0 +1,3 @@
+use distress_signal::{parse, part_one, part_two};
+
+aoc::examples!(parse, part_one, part_two);
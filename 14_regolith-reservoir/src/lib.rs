@@ -0,0 +1,207 @@
+//! Day 14: Regolith Reservoir.
+
+use std::cmp::max;
+
+use aoc::{Input, InputError};
+use log::{debug, log, log_enabled, trace, Level};
+
+pub type Cave = Vec<Vec<char>>;
+
+const EMPTY: char = '.';
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+struct Point {
+    col: usize,
+    row: usize,
+}
+
+/// The cave with the rock paths drawn in and the sand source at the top.
+pub fn parse(input: &Input) -> Result<Cave, InputError> {
+    let mut rocks = Vec::new();
+
+    let mut high_row = 0;
+    let mut high_col = 0;
+
+    for line in input.lines() {
+        let points = line
+            .split(" -> ")
+            .map(|p| {
+                let (col, row) = p
+                    .split_once(',')
+                    .and_then(|(col, row)| Some((col.parse::<usize>().ok()?, row.parse().ok()?)))
+                    .ok_or_else(|| line.error(format!("'{p}' isn't a point")))?;
+                high_col = max(col, high_col);
+                high_row = max(row, high_row);
+                Ok(Point { row, col })
+            })
+            .collect::<Result<Vec<Point>, InputError>>()?;
+
+        rocks.push(points);
+    }
+
+    high_col += 502;
+    high_row += 2;
+    debug!("{} columns, {} rows", high_col, high_row);
+
+    let mut cave = {
+        let mut row = Vec::new();
+        row.resize(high_col, EMPTY);
+
+        let mut cave = Vec::new();
+        cave.resize(high_row, row);
+        cave
+    };
+
+    for points in rocks {
+        let mut p = points.iter();
+        let mut start = *p.next().expect("split always gives one point");
+
+        trace!("{:?}", points);
+        for end in p {
+            loop {
+                cave[start.row][start.col] = '#';
+                let row_diff = start.row.cmp(&end.row);
+                let col_diff = start.col.cmp(&end.col);
+                match row_diff {
+                    std::cmp::Ordering::Less => start.row += 1,
+                    std::cmp::Ordering::Equal => (),
+                    std::cmp::Ordering::Greater => start.row -= 1,
+                };
+                match col_diff {
+                    std::cmp::Ordering::Less => start.col += 1,
+                    std::cmp::Ordering::Equal => (),
+                    std::cmp::Ordering::Greater => start.col -= 1,
+                };
+
+                if start == *end {
+                    cave[start.row][start.col] = '#';
+                    break;
+                }
+            }
+        }
+    }
+
+    cave[SOURCE.row][SOURCE.col] = '+';
+    Ok(cave)
+}
+
+enum NextPoint {
+    Abyss,
+    Spot(Point),
+    Stuck,
+}
+
+fn can_move(cave: &Cave, grain: Point) -> NextPoint {
+    let cave_height = cave.len() - 1;
+    let cave_width = cave[0].len() - 1;
+
+    if grain.row == cave_height {
+        return NextPoint::Abyss;
+    }
+
+    let next_row = grain.row + 1;
+
+    if cave[next_row][grain.col] == EMPTY {
+        return NextPoint::Spot(Point {
+            row: next_row,
+            col: grain.col,
+        });
+    }
+
+    if grain.col > 0 && cave[next_row][grain.col - 1] == EMPTY {
+        return NextPoint::Spot(Point {
+            row: next_row,
+            col: grain.col - 1,
+        });
+    }
+
+    if grain.col < cave_width && cave[next_row][grain.col + 1] == EMPTY {
+        return NextPoint::Spot(Point {
+            row: next_row,
+            col: grain.col + 1,
+        });
+    }
+
+    NextPoint::Stuck
+}
+
+/// How many grains of sand come to rest, either before they start flowing
+/// into the abyss or once the source is blocked.
+fn drop_sand(cave: &mut Cave, source: Point, trace: bool) -> usize {
+    let mut grain_count = 0;
+    loop {
+        let mut grain = source;
+        grain_count += 1;
+        loop {
+            match can_move(cave, grain) {
+                NextPoint::Abyss => {
+                    cave[grain.row][grain.col] = if !trace { EMPTY } else { '~' };
+                    log_cave(Level::Debug, cave, 0);
+                    return grain_count - 1;
+                }
+                NextPoint::Spot(point) => {
+                    cave[grain.row][grain.col] = if !trace { EMPTY } else { '~' };
+                    cave[point.row][point.col] = 'o';
+                    cave[source.row][source.col] = '+';
+                    grain = point;
+                }
+                NextPoint::Stuck => {
+                    if grain == source {
+                        log_cave(Level::Debug, cave, 1);
+                        return grain_count;
+                    }
+                    break;
+                }
+            }
+            log_cave(Level::Trace, cave, 0);
+        }
+    }
+}
+
+pub fn log_cave(level: Level, cave: &Cave, skip_last: usize) {
+    if !log_enabled!(level) {
+        return;
+    }
+    let mut low_col = usize::MAX;
+    for row in cave.iter().take(cave.len() - skip_last) {
+        low_col = std::cmp::min(
+            low_col,
+            row.iter()
+                .enumerate()
+                .find(|(_index, c)| **c != EMPTY)
+                .map(|(index, _c)| index)
+                .unwrap_or(usize::MAX),
+        );
+    }
+    low_col = low_col.saturating_sub(1);
+
+    let mut drawing = String::new();
+    for row in cave.iter().take(cave.len() - skip_last) {
+        drawing.extend(row.iter().skip(low_col));
+        drawing.push('\n');
+    }
+
+    log!(
+        level,
+        "{drawing}\n{} rows, low col is {}",
+        cave.len(),
+        low_col
+    );
+}
+
+const SOURCE: Point = Point { row: 0, col: 500 };
+
+/// The grains of sand that come to rest before sand flows into the abyss.
+pub fn part_one(cave: &Cave) -> usize {
+    // drop_sand(&mut cave.clone(), SOURCE, true)
+    drop_sand(&mut cave.clone(), SOURCE, false)
+}
+
+/// The grains of sand that come to rest on the floor before the source is
+/// blocked.
+pub fn part_two(cave: &Cave) -> usize {
+    let mut cave = cave.clone();
+    let mut floor = Vec::new();
+    floor.resize(cave[0].len(), '#');
+    cave.push(floor);
+    drop_sand(&mut cave, SOURCE, false)
+}
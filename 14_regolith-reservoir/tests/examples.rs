@@ -0,0 +1,3 @@
+use regolith_reservoir::{parse, part_one, part_two};
+
+aoc::examples!(parse, part_one, part_two);
@@ -0,0 +1,20 @@
+use beacon_exclusion_zone::{parse, part_one, part_two};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn parts(c: &mut Criterion) {
+    aoc::bench::parts(
+        c,
+        "15_beacon-exclusion-zone",
+        include_str!("../input.txt"),
+        parse,
+        part_one,
+        part_two,
+    );
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = parts
+}
+criterion_main!(benches);
@@ -0,0 +1,210 @@
+//! Day 15: Beacon Exclusion Zone.
+
+use std::{
+    cmp::{max, min},
+    collections::BTreeSet,
+};
+
+use aoc::{Input, InputError};
+use log::{debug, trace};
+use regex::Regex;
+
+#[derive(Copy, Clone, Debug)]
+pub struct Position {
+    pub col: i32,
+    pub row: i32,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct DevicePair {
+    sensor: Position,
+    beacon: Position,
+}
+
+impl DevicePair {
+    fn manhattan_distance(&self) -> i32 {
+        (self.sensor.row - self.beacon.row).abs() + (self.sensor.col - self.beacon.col).abs()
+    }
+
+    fn range_at_row(&self, row: i32) -> Option<Range> {
+        let distance_to_row = (self.sensor.row - row).abs();
+        let leftover = self.manhattan_distance() - distance_to_row;
+
+        if leftover < 0 {
+            return None;
+        }
+        Some(Range {
+            start: self.sensor.col - leftover,
+            end: self.sensor.col + leftover + 1,
+        })
+    }
+}
+
+#[derive(Copy, Clone, Eq, Ord, PartialEq, PartialOrd)]
+struct Range {
+    start: i32,
+    end: i32,
+}
+
+impl Range {
+    fn overlaps(&self, other: &Self) -> bool {
+        self.start <= other.end && self.end >= other.start
+    }
+
+    fn merge(&self, other: &Self) -> Range {
+        Range {
+            start: min(self.start, other.start),
+            end: max(self.end, other.end),
+        }
+    }
+
+    #[allow(dead_code)]
+    fn contains(&self, other: &Self) -> bool {
+        self.start <= other.start && self.end >= other.end
+    }
+}
+
+impl core::fmt::Debug for Range {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!("({}, {})", self.start, self.end))
+    }
+}
+
+/// Every sensor and the beacon closest to it.
+pub fn parse(input: &Input) -> Result<Vec<DevicePair>, InputError> {
+    let re =
+        Regex::new(r"^Sensor at x=(-?\d+), y=(-?\d+): closest beacon is at x=(-?\d+), y=(-?\d+)$")
+            .unwrap();
+
+    let mut devices = Vec::new();
+    for line in input.lines() {
+        let captures = re
+            .captures(&line)
+            .ok_or_else(|| line.error("expected a sensor and its closest beacon"))?;
+        let coordinate = |i: usize| {
+            captures[i]
+                .parse::<i32>()
+                .map_err(|e| line.error(format!("'{}': {e}", &captures[i])))
+        };
+        devices.push(DevicePair {
+            sensor: Position {
+                col: coordinate(1)?,
+                row: coordinate(2)?,
+            },
+            beacon: Position {
+                col: coordinate(3)?,
+                row: coordinate(4)?,
+            },
+        });
+    }
+
+    for device in devices.iter() {
+        debug!("{:?} distance: {}", device, device.manhattan_distance());
+    }
+    Ok(devices)
+}
+
+type Rect = (Position, Position);
+
+fn boundaries(devices: &[DevicePair]) -> Rect {
+    let tl = Position {
+        col: i32::MAX,
+        row: i32::MAX,
+    };
+    let br = Position {
+        col: i32::MIN,
+        row: i32::MIN,
+    };
+
+    devices
+        .iter()
+        .fold((tl, br), |(mut tl, mut br), device_pair| {
+            tl.row = min(tl.row, device_pair.sensor.row);
+            tl.row = min(tl.row, device_pair.beacon.row);
+            tl.col = min(tl.col, device_pair.sensor.col);
+            tl.col = min(tl.col, device_pair.beacon.col);
+            // Increment these by one so the boundary is exclusive
+            br.row = max(br.row, device_pair.sensor.row + 1);
+            br.row = max(br.row, device_pair.beacon.row + 1);
+            br.col = max(br.col, device_pair.sensor.col + 1);
+            br.col = max(br.col, device_pair.beacon.col + 1);
+
+            (tl, br)
+        })
+}
+
+/// The spots in `row` where there can't be a beacon.
+pub fn count_openings(row: i32, devices: &[DevicePair]) -> usize {
+    let mut not_beacons = BTreeSet::new();
+    for device in devices.iter() {
+        let distance_to_row = (device.sensor.row - row).abs();
+        let leftover = device.manhattan_distance() - distance_to_row;
+
+        trace!(
+            "Sensor position: {:?} covers columns {}..={}",
+            device.sensor,
+            device.sensor.col - leftover,
+            device.sensor.col + leftover
+        );
+        for i in device.sensor.col - leftover..device.sensor.col + leftover + 1 {
+            not_beacons.insert(i);
+        }
+    }
+
+    for device in devices.iter() {
+        if device.beacon.row == row {
+            not_beacons.remove(&device.beacon.col);
+        }
+    }
+    not_beacons.len()
+}
+
+/// The row the puzzle asks about. The example asks about row 10 instead.
+pub const QUESTION_ROW: i32 = 2000000;
+
+/// The spots in the row being asked about where there can't be a beacon.
+pub fn part_one(devices: &[DevicePair]) -> usize {
+    count_openings(QUESTION_ROW, devices)
+}
+
+/// The one spot, inside the sensors' boundary, that no sensor can see.
+pub fn distress_beacon(devices: &[DevicePair]) -> Option<Position> {
+    let boundary = boundaries(devices);
+    debug!("Boundary: {:?}", boundary);
+
+    for row in boundary.0.row..boundary.1.row {
+        let mut ranges: Vec<Range> = devices
+            .iter()
+            .filter_map(|device| device.range_at_row(row))
+            .collect();
+        ranges.sort();
+
+        trace!("{:3} {:?}", row, ranges);
+
+        let mut known = ranges[0];
+        for range in ranges.iter().skip(1) {
+            if known.overlaps(range) {
+                known = known.merge(range);
+            } else {
+                debug!(
+                    "row {} doesn't overlap known range: {:?} at range {:?}",
+                    row, known, range
+                );
+
+                return Some(Position {
+                    col: known.end,
+                    row,
+                });
+                // known = *range;
+            }
+        }
+        trace!("Mega Range: {:?}\n", known)
+    }
+
+    None
+}
+
+/// The distress beacon's tuning frequency.
+pub fn part_two(devices: &[DevicePair]) -> Option<i64> {
+    distress_beacon(devices).map(|beacon| beacon.col as i64 * 4000000 + beacon.row as i64)
+}
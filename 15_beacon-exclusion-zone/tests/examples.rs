@@ -0,0 +1,8 @@
+use beacon_exclusion_zone::{count_openings, parse, part_two, DevicePair};
+
+// The example asks about row 10 rather than the puzzle's row.
+aoc::examples!(
+    parse,
+    |devices: &[DevicePair]| count_openings(10, devices),
+    part_two
+);
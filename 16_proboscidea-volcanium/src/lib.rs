@@ -0,0 +1,605 @@
+//! Day 16: Proboscidea Volcanium.
+
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fmt::Display,
+};
+
+use aoc::{Input, InputError};
+use derive_more::{Add, AddAssign, Mul};
+use regex::Regex;
+
+#[derive(Debug, Clone)]
+pub struct Valve {
+    name: String,
+    flow_rate: usize,
+    neighbors: Vec<String>,
+}
+
+/// Every valve by name, checked to only lead to valves that exist.
+pub fn parse(input: &Input) -> Result<BTreeMap<String, Valve>, InputError> {
+    let re =
+        Regex::new(r"^Valve (\w+) has flow rate=(\d+); tunnels? leads? to valves? (.*)$").unwrap();
+
+    let mut valves = BTreeMap::new();
+    let mut lines = BTreeMap::new();
+    for line in input.lines() {
+        let captures = re
+            .captures(&line)
+            .ok_or_else(|| line.error("expected a valve, its flow rate and its tunnels"))?;
+        let name = captures[1].to_owned();
+        let flow_rate = captures[2]
+            .parse::<usize>()
+            .map_err(|e| line.error(format!("'{}': {e}", &captures[2])))?;
+        let neighbors: Vec<String> = captures[3].split(", ").map(str::to_owned).collect();
+        lines.insert(name.clone(), line);
+        valves.insert(
+            name.clone(),
+            Valve {
+                name,
+                flow_rate,
+                neighbors,
+            },
+        );
+    }
+
+    for valve in valves.values() {
+        if let Some(neighbor) = valve
+            .neighbors
+            .iter()
+            .find(|neighbor| !valves.contains_key(*neighbor))
+        {
+            return Err(lines[&valve.name].error(format!("there is no valve {neighbor}")));
+        }
+    }
+    Ok(valves)
+}
+
+#[derive(Add, AddAssign, Mul, Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub struct PressureReleased(pub usize);
+
+/// One bit per pressurized valve, spread over as many words as it takes.
+#[derive(Clone, Debug, Default, Eq, PartialEq, PartialOrd, Ord)]
+struct ValveState(Vec<u64>);
+
+impl ValveState {
+    fn is_open(&self, valve: usize) -> bool {
+        self.0
+            .get(valve / 64)
+            .is_some_and(|word| word & (1 << (valve % 64)) != 0)
+    }
+
+    fn open(&self, valve: usize) -> ValveState {
+        let mut words = self.0.clone();
+        if words.len() <= valve / 64 {
+            words.resize(valve / 64 + 1, 0);
+        }
+        words[valve / 64] |= 1 << (valve % 64);
+        ValveState(words)
+    }
+
+    fn is_disjoint(&self, other: &ValveState) -> bool {
+        self.0.iter().zip(other.0.iter()).all(|(a, b)| a & b == 0)
+    }
+
+    fn union(&self, other: &ValveState) -> ValveState {
+        let (longer, shorter) = if self.0.len() >= other.0.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let mut words = longer.0.clone();
+        for (word, other_word) in words.iter_mut().zip(shorter.0.iter()) {
+            *word |= other_word;
+        }
+        ValveState(words)
+    }
+}
+
+/// The distance between valves with no tunnels leading from one to the other,
+/// which is never within the time left.
+const UNREACHABLE: usize = usize::MAX;
+
+/// The tunnel graph reduced to the valves worth opening plus the start valve.
+/// Pressurized valves take indices `0..flow_rates.len()` so their index is also
+/// their bit in a `ValveState`; the start valve is appended if it has no flow.
+pub struct ValveGraph {
+    names: Vec<String>,
+    flow_rates: Vec<usize>,
+    distances: Vec<Vec<usize>>,
+    start: usize,
+}
+
+impl ValveGraph {
+    pub fn new(valves: &BTreeMap<String, Valve>, start: &str) -> ValveGraph {
+        let mut names: Vec<String> = valves
+            .values()
+            .filter(|valve| valve.flow_rate > 0)
+            .map(|valve| valve.name.clone())
+            .collect();
+        let flow_rates: Vec<usize> = names.iter().map(|name| valves[name].flow_rate).collect();
+        let start = match names.iter().position(|name| name == start) {
+            Some(index) => index,
+            None => {
+                names.push(start.to_owned());
+                names.len() - 1
+            }
+        };
+
+        let distances = names
+            .iter()
+            .map(|from| {
+                let distances = tunnel_distances(valves, from);
+                names
+                    .iter()
+                    .map(|to| distances.get(to).copied().unwrap_or(UNREACHABLE))
+                    .collect()
+            })
+            .collect();
+
+        ValveGraph {
+            names,
+            flow_rates,
+            distances,
+            start,
+        }
+    }
+
+    fn pressurized_valve_count(&self) -> usize {
+        self.flow_rates.len()
+    }
+
+    /// An optimistic bound on what is still to come: every closed valve is opened
+    /// as soon as it could possibly be reached from `position`.
+    fn upper_bound(&self, position: usize, minutes_left: usize, valve_state: &ValveState) -> usize {
+        (0..self.pressurized_valve_count())
+            .filter(|valve| !valve_state.is_open(*valve))
+            .map(|valve| {
+                let open_at = self.distances[position][valve].saturating_add(1);
+                self.flow_rates[valve] * minutes_left.saturating_sub(open_at)
+            })
+            .sum()
+    }
+}
+
+/// Breadth first walk of the full tunnel graph, one minute per tunnel.
+fn tunnel_distances(valves: &BTreeMap<String, Valve>, from: &str) -> BTreeMap<String, usize> {
+    let mut distances = BTreeMap::from([(from.to_owned(), 0)]);
+    let mut queue = VecDeque::from([from.to_owned()]);
+    while let Some(name) = queue.pop_front() {
+        let distance = distances[&name];
+        for neighbor in valves[&name].neighbors.iter() {
+            if !distances.contains_key(neighbor) {
+                distances.insert(neighbor.clone(), distance + 1);
+                queue.push_back(neighbor.clone());
+            }
+        }
+    }
+    distances
+}
+
+/// The valves each agent opens, in order, as indices into the `ValveGraph`.
+#[derive(Clone, Debug)]
+pub struct Solution {
+    pub pressure_released: PressureReleased,
+    routes: Vec<Vec<usize>>,
+}
+
+fn find_best_path_from(
+    graph: &ValveGraph,
+    position: usize,
+    minutes_left: usize,
+    valve_state: &ValveState,
+    pressure_released: PressureReleased,
+    route: &mut Vec<usize>,
+    best: &mut Solution,
+) {
+    if pressure_released > best.pressure_released {
+        best.pressure_released = pressure_released;
+        best.routes = vec![route.clone()];
+    }
+    let bound = graph.upper_bound(position, minutes_left, valve_state);
+    if pressure_released + PressureReleased(bound) <= best.pressure_released {
+        return;
+    }
+
+    for valve in 0..graph.pressurized_valve_count() {
+        let open_at = graph.distances[position][valve].saturating_add(1);
+        if valve_state.is_open(valve) || open_at >= minutes_left {
+            continue;
+        }
+        let minutes_left = minutes_left - open_at;
+        route.push(valve);
+        find_best_path_from(
+            graph,
+            valve,
+            minutes_left,
+            &valve_state.open(valve),
+            pressure_released + PressureReleased(graph.flow_rates[valve] * minutes_left),
+            route,
+            best,
+        );
+        route.pop();
+    }
+}
+
+fn find_best_path(graph: &ValveGraph, minutes: usize) -> Solution {
+    let mut best = Solution {
+        pressure_released: PressureReleased(0),
+        routes: vec![Vec::new()],
+    };
+    find_best_path_from(
+        graph,
+        graph.start,
+        minutes,
+        &ValveState::default(),
+        PressureReleased(0),
+        &mut Vec::new(),
+        &mut best,
+    );
+    best
+}
+
+/// Records the most pressure a single agent can release while opening exactly
+/// the valves in each `ValveState`, along with the route that does it. Reaching
+/// the same (position, minutes left, valve state) with no more pressure than
+/// before can't improve on any entry.
+#[allow(clippy::too_many_arguments)]
+fn find_best_pressure_per_valve_state(
+    graph: &ValveGraph,
+    position: usize,
+    minutes_left: usize,
+    valve_state: &ValveState,
+    pressure_released: PressureReleased,
+    route: &mut Vec<usize>,
+    best_per_valve_state: &mut BTreeMap<ValveState, (PressureReleased, Vec<usize>)>,
+    seen: &mut BTreeMap<(usize, usize, ValveState), PressureReleased>,
+) {
+    let key = (position, minutes_left, valve_state.clone());
+    if let Some(most_pressure_released) = seen.get(&key) {
+        if *most_pressure_released >= pressure_released {
+            return;
+        }
+    }
+    seen.insert(key, pressure_released);
+
+    let best = best_per_valve_state
+        .entry(valve_state.clone())
+        .or_insert((PressureReleased(0), Vec::new()));
+    if pressure_released > best.0 {
+        *best = (pressure_released, route.clone());
+    }
+
+    for valve in 0..graph.pressurized_valve_count() {
+        let open_at = graph.distances[position][valve].saturating_add(1);
+        if valve_state.is_open(valve) || open_at >= minutes_left {
+            continue;
+        }
+        let minutes_left = minutes_left - open_at;
+        route.push(valve);
+        find_best_pressure_per_valve_state(
+            graph,
+            valve,
+            minutes_left,
+            &valve_state.open(valve),
+            pressure_released + PressureReleased(graph.flow_rates[valve] * minutes_left),
+            route,
+            best_per_valve_state,
+            seen,
+        );
+        route.pop();
+    }
+}
+
+/// Hands out pairwise disjoint candidates to the agents that are left.
+/// Candidates are sorted by pressure, so once the next one can't lift the rest
+/// of the team past the best found nothing after it can either.
+fn find_best_team_from(
+    candidates: &[(PressureReleased, ValveState, Vec<usize>)],
+    agents_left: usize,
+    used: &ValveState,
+    pressure_released: PressureReleased,
+    chosen: &mut Vec<usize>,
+    best: &mut (PressureReleased, Vec<usize>),
+) {
+    if pressure_released > best.0 {
+        *best = (pressure_released, chosen.clone());
+    }
+    if agents_left == 0 {
+        return;
+    }
+
+    for (i, (candidate_pressure, valve_state, _route)) in candidates.iter().enumerate() {
+        if pressure_released + *candidate_pressure * agents_left <= best.0 {
+            break;
+        }
+        if !valve_state.is_disjoint(used) {
+            continue;
+        }
+        chosen.push(i);
+        find_best_team_from(
+            &candidates[i + 1..],
+            agents_left - 1,
+            &used.union(valve_state),
+            pressure_released + *candidate_pressure,
+            chosen,
+            best,
+        );
+        chosen.pop();
+    }
+}
+
+/// Agents never open the same valve, so the best team result is the best set
+/// of pairwise disjoint valve states from the single-agent search.
+pub fn find_best_team_path(graph: &ValveGraph, agents: usize, minutes: usize) -> Solution {
+    if agents == 1 {
+        return find_best_path(graph, minutes);
+    }
+
+    let mut best_per_valve_state = BTreeMap::new();
+    find_best_pressure_per_valve_state(
+        graph,
+        graph.start,
+        minutes,
+        &ValveState::default(),
+        PressureReleased(0),
+        &mut Vec::new(),
+        &mut best_per_valve_state,
+        &mut BTreeMap::new(),
+    );
+
+    let mut candidates: Vec<(PressureReleased, ValveState, Vec<usize>)> = best_per_valve_state
+        .into_iter()
+        .map(|(valve_state, (pressure_released, route))| (pressure_released, valve_state, route))
+        .collect();
+    candidates.sort_by_key(|candidate| std::cmp::Reverse(candidate.0));
+
+    // `chosen` indexes into the slice each level was handed, so walk it back
+    // down to indices into `candidates`.
+    let mut best = (PressureReleased(0), Vec::new());
+    find_best_team_from(
+        &candidates,
+        agents,
+        &ValveState::default(),
+        PressureReleased(0),
+        &mut Vec::new(),
+        &mut best,
+    );
+    let mut routes = Vec::new();
+    let mut offset = 0;
+    for i in best.1 {
+        routes.push(candidates[offset + i].2.clone());
+        offset += i + 1;
+    }
+    routes.resize(agents, Vec::new());
+
+    Solution {
+        pressure_released: best.0,
+        routes,
+    }
+}
+
+/// Breadth first walk of the full tunnel graph returning the valves passed
+/// through on a shortest path from `from` to `to`, excluding `from`.
+fn tunnel_path(valves: &BTreeMap<String, Valve>, from: &str, to: &str) -> Vec<String> {
+    let mut came_from: BTreeMap<String, String> = BTreeMap::new();
+    let mut queue = VecDeque::from([from.to_owned()]);
+    while let Some(name) = queue.pop_front() {
+        if name == to {
+            break;
+        }
+        for neighbor in valves[&name].neighbors.iter() {
+            if neighbor != from && !came_from.contains_key(neighbor) {
+                came_from.insert(neighbor.clone(), name.clone());
+                queue.push_back(neighbor.clone());
+            }
+        }
+    }
+
+    let mut path = Vec::new();
+    let mut name = to.to_owned();
+    while name != from {
+        let previous = came_from[&name].clone();
+        path.push(name);
+        name = previous;
+    }
+    path.reverse();
+    path
+}
+
+#[derive(Clone, Debug)]
+enum Action {
+    Move(String),
+    Open(String),
+    Wait,
+}
+
+#[derive(Debug)]
+struct Minute {
+    open_valves: Vec<String>,
+    pressure_released: PressureReleased,
+    actions: Vec<Action>,
+}
+
+/// What every agent does in every minute of a `Solution`.
+#[derive(Debug)]
+pub struct Schedule {
+    minutes: Vec<Minute>,
+}
+
+impl Schedule {
+    pub fn new(
+        valves: &BTreeMap<String, Valve>,
+        graph: &ValveGraph,
+        solution: &Solution,
+        minutes: usize,
+    ) -> Schedule {
+        let agent_actions: Vec<Vec<Action>> = solution
+            .routes
+            .iter()
+            .map(|route| {
+                let mut actions = Vec::new();
+                let mut from = &graph.names[graph.start];
+                for valve in route.iter() {
+                    let to = &graph.names[*valve];
+                    actions.extend(tunnel_path(valves, from, to).into_iter().map(Action::Move));
+                    actions.push(Action::Open(to.clone()));
+                    from = to;
+                }
+                actions.resize(minutes, Action::Wait);
+                actions
+            })
+            .collect();
+
+        let mut open_valves: Vec<String> = Vec::new();
+        let mut schedule = Schedule {
+            minutes: Vec::new(),
+        };
+        for minute in 0..minutes {
+            let actions: Vec<Action> = agent_actions
+                .iter()
+                .map(|actions| actions[minute].clone())
+                .collect();
+            let pressure_released = open_valves
+                .iter()
+                .map(|name| PressureReleased(valves[name].flow_rate))
+                .fold(PressureReleased(0), |sum, flow| sum + flow);
+            schedule.minutes.push(Minute {
+                open_valves: open_valves.clone(),
+                pressure_released,
+                actions: actions.clone(),
+            });
+
+            for action in actions {
+                if let Action::Open(name) = action {
+                    open_valves.push(name);
+                }
+            }
+            open_valves.sort();
+        }
+        schedule
+    }
+
+    pub fn pressure_released(&self) -> PressureReleased {
+        self.minutes
+            .iter()
+            .fold(PressureReleased(0), |sum, minute| {
+                sum + minute.pressure_released
+            })
+    }
+}
+
+impl Display for Schedule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, minute) in self.minutes.iter().enumerate() {
+            writeln!(f, "== Minute {} ==", i + 1)?;
+            match minute.open_valves.as_slice() {
+                [] => writeln!(f, "No valves are open.")?,
+                [valve] => writeln!(
+                    f,
+                    "Valve {} is open, releasing {} pressure.",
+                    valve, minute.pressure_released.0
+                )?,
+                [first, second] => writeln!(
+                    f,
+                    "Valves {} and {} are open, releasing {} pressure.",
+                    first, second, minute.pressure_released.0
+                )?,
+                [rest @ .., last] => writeln!(
+                    f,
+                    "Valves {}, and {} are open, releasing {} pressure.",
+                    rest.join(", "),
+                    last,
+                    minute.pressure_released.0
+                )?,
+            }
+
+            for (agent, action) in minute.actions.iter().enumerate() {
+                let (who, move_verb, open_verb) = match (agent, minute.actions.len()) {
+                    (0, _) => ("You".to_owned(), "move", "open"),
+                    (_, 2) => ("The elephant".to_owned(), "moves", "opens"),
+                    _ => (format!("Elephant {}", agent), "moves", "opens"),
+                };
+                match action {
+                    Action::Move(name) => writeln!(f, "{} {} to valve {}.", who, move_verb, name)?,
+                    Action::Open(name) => writeln!(f, "{} {} valve {}.", who, open_verb, name)?,
+                    Action::Wait => {}
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+const ROUTE_COLORS: [&str; 4] = ["red", "blue", "darkgreen", "orange"];
+
+/// Renders the tunnels as an undirected Graphviz graph. Valves with no flow are
+/// drawn as small grey points. If a solution is given each agent's opening
+/// order is overlaid as numbered, coloured edges between the valves it opens.
+pub fn write_dot(
+    mut w: impl std::io::Write,
+    valves: &BTreeMap<String, Valve>,
+    graph: &ValveGraph,
+    solution: Option<&Solution>,
+) -> std::io::Result<()> {
+    writeln!(w, "graph valves {{")?;
+    for valve in valves.values() {
+        if valve.flow_rate > 0 {
+            writeln!(
+                w,
+                "    {} [label=\"{}\\n{}\"];",
+                valve.name, valve.name, valve.flow_rate
+            )?;
+        } else {
+            writeln!(
+                w,
+                "    {} [shape=point, color=grey, xlabel=\"{}\"];",
+                valve.name, valve.name
+            )?;
+        }
+    }
+
+    for valve in valves.values() {
+        for neighbor in valve
+            .neighbors
+            .iter()
+            .filter(|neighbor| valve.name < **neighbor)
+        {
+            writeln!(w, "    {} -- {};", valve.name, neighbor)?;
+        }
+    }
+
+    if let Some(solution) = solution {
+        for (agent, route) in solution.routes.iter().enumerate() {
+            let color = ROUTE_COLORS[agent % ROUTE_COLORS.len()];
+            let mut from = graph.start;
+            for (step, to) in route.iter().enumerate() {
+                writeln!(
+                    w,
+                    "    {} -- {} [label=\"{}\", color={}, fontcolor={}, dir=forward, style=bold, constraint=false];",
+                    graph.names[from],
+                    graph.names[*to],
+                    step + 1,
+                    color,
+                    color
+                )?;
+                from = *to;
+            }
+        }
+    }
+
+    writeln!(w, "}}")
+}
+
+/// The most pressure one agent can release in 30 minutes from valve AA.
+pub fn part_one(valves: &BTreeMap<String, Valve>) -> usize {
+    let graph = ValveGraph::new(valves, "AA");
+    find_best_team_path(&graph, 1, 30).pressure_released.0
+}
+
+/// The most pressure two agents can release in 26 minutes from valve AA.
+pub fn part_two(valves: &BTreeMap<String, Valve>) -> usize {
+    let graph = ValveGraph::new(valves, "AA");
+    find_best_team_path(&graph, 2, 26).pressure_released.0
+}
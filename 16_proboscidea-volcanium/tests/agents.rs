@@ -0,0 +1,48 @@
+use aoc::Input;
+use proboscidea_volcanium::{find_best_team_path, parse, ValveGraph};
+
+/// The most pressure `agents` agents starting at `start` release from the
+/// example's valves in `minutes` minutes.
+fn example(start: &str, agents: usize, minutes: usize) -> usize {
+    let valves = parse(&Input::new(
+        "example",
+        include_str!("../examples/example.txt"),
+    ))
+    .unwrap();
+    let graph = ValveGraph::new(&valves, start);
+    find_best_team_path(&graph, agents, minutes)
+        .pressure_released
+        .0
+}
+
+#[test]
+fn one_agent() {
+    assert_eq!(example("AA", 1, 30), 1651);
+    assert_eq!(example("AA", 1, 26), 1327);
+}
+
+#[test]
+fn two_agents() {
+    assert_eq!(example("AA", 2, 26), 1707);
+    assert_eq!(example("AA", 2, 30), 2031);
+}
+
+#[test]
+fn three_agents() {
+    assert_eq!(example("AA", 3, 26), 1794);
+    assert_eq!(example("AA", 3, 30), 2118);
+}
+
+#[test]
+fn starting_at_a_pressurized_valve() {
+    assert_eq!(example("DD", 1, 30), 1732);
+    assert_eq!(example("DD", 2, 26), 1716);
+    assert_eq!(example("DD", 3, 26), 1801);
+}
+
+#[test]
+fn starting_at_a_dead_end() {
+    assert_eq!(example("JJ", 1, 30), 1807);
+    assert_eq!(example("JJ", 2, 26), 1698);
+    assert_eq!(example("JJ", 3, 26), 1732);
+}
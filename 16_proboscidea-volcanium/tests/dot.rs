@@ -0,0 +1,93 @@
+use aoc::Input;
+use proboscidea_volcanium::{find_best_team_path, parse, write_dot, ValveGraph};
+
+/// The example's DOT graph, with the opening order of `agents` agents in
+/// `minutes` minutes overlaid if given.
+fn example_dot(overlay: Option<(usize, usize)>) -> String {
+    let valves = parse(&Input::new(
+        "example",
+        include_str!("../examples/example.txt"),
+    ))
+    .unwrap();
+    let graph = ValveGraph::new(&valves, "AA");
+    let solution = overlay.map(|(agents, minutes)| find_best_team_path(&graph, agents, minutes));
+    let mut dot = Vec::new();
+    write_dot(&mut dot, &valves, &graph, solution.as_ref()).unwrap();
+    String::from_utf8(dot).unwrap()
+}
+
+/// The lines of the graph body that contain `pattern`.
+fn lines_with<'a>(dot: &'a str, pattern: &str) -> Vec<&'a str> {
+    dot.lines()
+        .map(str::trim)
+        .filter(|line| line.contains(pattern))
+        .collect()
+}
+
+#[test]
+fn dot_nodes_are_labelled_with_flow_rates() {
+    let dot = example_dot(None);
+    assert!(dot.starts_with("graph valves {\n"));
+    assert!(dot.ends_with("}\n"));
+    assert_eq!(
+        lines_with(&dot, " [label=\""),
+        [
+            "BB [label=\"BB\\n13\"];",
+            "CC [label=\"CC\\n2\"];",
+            "DD [label=\"DD\\n20\"];",
+            "EE [label=\"EE\\n3\"];",
+            "HH [label=\"HH\\n22\"];",
+            "JJ [label=\"JJ\\n21\"];",
+        ]
+    );
+    assert_eq!(
+        lines_with(&dot, "shape=point"),
+        [
+            "AA [shape=point, color=grey, xlabel=\"AA\"];",
+            "FF [shape=point, color=grey, xlabel=\"FF\"];",
+            "GG [shape=point, color=grey, xlabel=\"GG\"];",
+            "II [shape=point, color=grey, xlabel=\"II\"];",
+        ]
+    );
+}
+
+#[test]
+fn dot_draws_each_tunnel_once() {
+    let dot = example_dot(None);
+    assert_eq!(
+        lines_with(&dot, " -- "),
+        [
+            "AA -- DD;",
+            "AA -- II;",
+            "AA -- BB;",
+            "BB -- CC;",
+            "CC -- DD;",
+            "DD -- EE;",
+            "EE -- FF;",
+            "FF -- GG;",
+            "GG -- HH;",
+            "II -- JJ;",
+        ]
+    );
+}
+
+#[test]
+fn dot_overlays_the_opening_order() {
+    let dot = example_dot(Some((2, 26)));
+    let overlay: Vec<&str> = lines_with(&dot, "dir=forward")
+        .into_iter()
+        .map(|line| &line[..line.find(", fontcolor").unwrap()])
+        .collect();
+    assert_eq!(
+        overlay,
+        [
+            "AA -- DD [label=\"1\", color=red",
+            "DD -- HH [label=\"2\", color=red",
+            "HH -- EE [label=\"3\", color=red",
+            "AA -- JJ [label=\"1\", color=blue",
+            "JJ -- BB [label=\"2\", color=blue",
+            "BB -- CC [label=\"3\", color=blue",
+        ]
+    );
+    assert_eq!(lines_with(&dot, " -- ").len(), 10 + 6);
+}
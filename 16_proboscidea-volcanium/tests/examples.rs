@@ -0,0 +1,3 @@
+use proboscidea_volcanium::{parse, part_one, part_two};
+
+aoc::examples!(parse, part_one, part_two);
@@ -0,0 +1,82 @@
+use aoc::Input;
+use proboscidea_volcanium::{find_best_team_path, parse, Schedule, ValveGraph};
+
+fn example_schedule(agents: usize, minutes: usize) -> Schedule {
+    let valves = parse(&Input::new(
+        "example",
+        include_str!("../examples/example.txt"),
+    ))
+    .unwrap();
+    let graph = ValveGraph::new(&valves, "AA");
+    let solution = find_best_team_path(&graph, agents, minutes);
+    Schedule::new(&valves, &graph, &solution, minutes)
+}
+
+/// The minute each valve is opened in and who opens it, read back out of the
+/// narration.
+fn openings(schedule: &Schedule) -> Vec<(usize, String, String)> {
+    let mut minute = 0;
+    let mut openings = Vec::new();
+    for line in schedule.to_string().lines() {
+        if let Some(n) = line.strip_prefix("== Minute ") {
+            minute = n.trim_end_matches(" ==").parse().unwrap();
+        } else if let Some((who, valve)) = line.split_once(" valve ") {
+            if let Some(who) = who
+                .strip_suffix(" open")
+                .or_else(|| who.strip_suffix(" opens"))
+            {
+                openings.push((
+                    minute,
+                    who.to_owned(),
+                    valve.trim_end_matches('.').to_owned(),
+                ));
+            }
+        }
+    }
+    openings
+}
+
+#[test]
+fn example_schedule_on_your_own() {
+    let schedule = example_schedule(1, 30);
+    let expected = [
+        (2, "DD"),
+        (5, "BB"),
+        (9, "JJ"),
+        (17, "HH"),
+        (21, "EE"),
+        (24, "CC"),
+    ];
+    assert_eq!(
+        openings(&schedule),
+        expected.map(|(minute, valve)| (minute, "You".to_owned(), valve.to_owned()))
+    );
+    assert_eq!(schedule.pressure_released().0, 1651);
+
+    let text = schedule.to_string();
+    assert!(text.starts_with("== Minute 1 ==\nNo valves are open.\nYou move to valve DD.\n\n"));
+    assert!(text.contains(
+        "== Minute 20 ==\nValves BB, DD, HH, and JJ are open, releasing 76 pressure.\nYou move to valve EE.\n\n"
+    ));
+    assert!(text.ends_with(
+        "== Minute 30 ==\nValves BB, CC, DD, EE, HH, and JJ are open, releasing 81 pressure.\n\n"
+    ));
+}
+
+#[test]
+fn example_schedule_with_the_elephant() {
+    let schedule = example_schedule(2, 26);
+    let expected = [
+        (2, "You", "DD"),
+        (3, "The elephant", "JJ"),
+        (7, "You", "HH"),
+        (7, "The elephant", "BB"),
+        (9, "The elephant", "CC"),
+        (11, "You", "EE"),
+    ];
+    assert_eq!(
+        openings(&schedule),
+        expected.map(|(minute, who, valve)| (minute, who.to_owned(), valve.to_owned()))
+    );
+    assert_eq!(schedule.pressure_released().0, 1707);
+}
@@ -0,0 +1,16 @@
+use boiling_boulders::{parse, part_one, part_two};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn parts(c: &mut Criterion) {
+    aoc::bench::parts(
+        c,
+        "18_boiling-boulders",
+        include_str!("../input.txt"),
+        parse,
+        part_one,
+        part_two,
+    );
+}
+
+criterion_group!(benches, parts);
+criterion_main!(benches);
@@ -0,0 +1,218 @@
+//! Day 18: Boiling Boulders.
+
+use std::{
+    cmp::{max, min},
+    collections::HashMap,
+};
+
+use aoc::{Input, InputError};
+use log::{debug, trace};
+use regex::Regex;
+
+/// A cube of lava, at x, y and z.
+pub type Cube = (i32, i32, i32);
+
+pub fn parse(input: &Input) -> Result<Vec<Cube>, InputError> {
+    let re = Regex::new(r"^(\d+),(\d+),(\d+)$").unwrap();
+    input
+        .lines()
+        .map(|line| {
+            let captures = re
+                .captures(&line)
+                .ok_or_else(|| line.error("expected a cube's x,y,z"))?;
+            let coordinate = |i: usize| {
+                captures[i]
+                    .parse::<i32>()
+                    .map_err(|e| line.error(format!("'{}': {e}", &captures[i])))
+            };
+            Ok((coordinate(1)?, coordinate(2)?, coordinate(3)?))
+        })
+        .collect()
+}
+
+/// The surface area of the lava droplet, including any air pockets inside.
+pub fn part_one(cubes: &[Cube]) -> i32 {
+    let mut points: HashMap<(i32, i32, i32), i32> = HashMap::new();
+    for &(x, y, z) in cubes {
+        let mut point = 6;
+        if let Some(other) = points.get_mut(&(x - 1, y, z)) {
+            *other -= 1;
+            point -= 1;
+        }
+        if let Some(other) = points.get_mut(&(x + 1, y, z)) {
+            *other -= 1;
+            point -= 1;
+        }
+        if let Some(other) = points.get_mut(&(x, y - 1, z)) {
+            *other -= 1;
+            point -= 1;
+        }
+        if let Some(other) = points.get_mut(&(x, y + 1, z)) {
+            *other -= 1;
+            point -= 1;
+        }
+        if let Some(other) = points.get_mut(&(x, y, z - 1)) {
+            *other -= 1;
+            point -= 1;
+        }
+        if let Some(other) = points.get_mut(&(x, y, z + 1)) {
+            *other -= 1;
+            point -= 1;
+        }
+        points.insert((x, y, z), point);
+        trace!("({x}, {y}, {z}) = {point}");
+    }
+    points.values().sum()
+}
+
+enum Point {
+    Steam,
+    Lava(i32),
+}
+
+/// The surface area of the lava droplet that steam can reach.
+pub fn part_two(cubes: &[Cube]) -> i32 {
+    let mut points: HashMap<(i32, i32, i32), Point> = HashMap::new();
+
+    let mut minx = i32::MAX;
+    let mut maxx = i32::MIN;
+    let mut miny = i32::MAX;
+    let mut maxy = i32::MIN;
+    let mut minz = i32::MAX;
+    let mut maxz = i32::MIN;
+
+    for &(x, y, z) in cubes {
+        let mut point = 6;
+        if let Some(Point::Lava(other)) = points.get_mut(&(x - 1, y, z)) {
+            *other -= 1;
+            point -= 1;
+        }
+        if let Some(Point::Lava(other)) = points.get_mut(&(x + 1, y, z)) {
+            *other -= 1;
+            point -= 1;
+        }
+        if let Some(Point::Lava(other)) = points.get_mut(&(x, y - 1, z)) {
+            *other -= 1;
+            point -= 1;
+        }
+        if let Some(Point::Lava(other)) = points.get_mut(&(x, y + 1, z)) {
+            *other -= 1;
+            point -= 1;
+        }
+        if let Some(Point::Lava(other)) = points.get_mut(&(x, y, z - 1)) {
+            *other -= 1;
+            point -= 1;
+        }
+        if let Some(Point::Lava(other)) = points.get_mut(&(x, y, z + 1)) {
+            *other -= 1;
+            point -= 1;
+        }
+
+        points.insert((x, y, z), Point::Lava(point));
+
+        minx = min(minx, x);
+        miny = min(miny, y);
+        minz = min(minz, z);
+        maxx = max(maxx, x);
+        maxy = max(maxy, y);
+        maxz = max(maxz, z);
+    }
+
+    maxx += 1;
+    maxy += 1;
+    maxz += 1;
+    debug!("({minx}, {miny}, {minz}), ({maxx}, {maxy}, {maxz})");
+
+    for y in miny..maxy {
+        for z in minz..maxz {
+            points.insert((minx - 1, y, z), Point::Steam);
+            points.insert((maxx, y, z), Point::Steam);
+        }
+    }
+    for x in minx..maxz {
+        for z in minz..maxz {
+            points.insert((x, miny - 1, z), Point::Steam);
+            points.insert((x, maxy, z), Point::Steam);
+        }
+    }
+    for x in minx..maxx {
+        for y in miny..maxy {
+            points.insert((x, y, minz - 1), Point::Steam);
+            points.insert((x, y, maxz), Point::Steam);
+        }
+    }
+
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for x in minx..maxx {
+            for y in miny..maxy {
+                for z in minz..maxz {
+                    if !points.contains_key(&(x, y, z)) && adjacent_to_steam(x, y, z, &points) {
+                        points.insert((x, y, z), Point::Steam);
+                        changed = true;
+                    }
+                }
+            }
+        }
+    }
+
+    for x in minx..maxx {
+        for y in miny..maxy {
+            for z in minz..maxz {
+                if !points.contains_key(&(x, y, z)) {
+                    trace!("Bubble ({x}, {y}, {z})");
+
+                    if let Some(Point::Lava(other)) = points.get_mut(&(x - 1, y, z)) {
+                        *other -= 1;
+                    }
+                    if let Some(Point::Lava(other)) = points.get_mut(&(x + 1, y, z)) {
+                        *other -= 1;
+                    }
+                    if let Some(Point::Lava(other)) = points.get_mut(&(x, y - 1, z)) {
+                        *other -= 1;
+                    }
+                    if let Some(Point::Lava(other)) = points.get_mut(&(x, y + 1, z)) {
+                        *other -= 1;
+                    }
+                    if let Some(Point::Lava(other)) = points.get_mut(&(x, y, z - 1)) {
+                        *other -= 1;
+                    }
+                    if let Some(Point::Lava(other)) = points.get_mut(&(x, y, z + 1)) {
+                        *other -= 1;
+                    }
+                }
+            }
+        }
+    }
+
+    let mut total = 0;
+    for point in points.values() {
+        if let Point::Lava(sides) = point {
+            total += sides;
+        }
+    }
+    total
+}
+
+fn adjacent_to_steam(x: i32, y: i32, z: i32, points: &HashMap<(i32, i32, i32), Point>) -> bool {
+    if let Some(Point::Steam) = points.get(&(x - 1, y, z)) {
+        return true;
+    }
+    if let Some(Point::Steam) = points.get(&(x + 1, y, z)) {
+        return true;
+    }
+    if let Some(Point::Steam) = points.get(&(x, y - 1, z)) {
+        return true;
+    }
+    if let Some(Point::Steam) = points.get(&(x, y + 1, z)) {
+        return true;
+    }
+    if let Some(Point::Steam) = points.get(&(x, y, z - 1)) {
+        return true;
+    }
+    if let Some(Point::Steam) = points.get(&(x, y, z + 1)) {
+        return true;
+    }
+    false
+}
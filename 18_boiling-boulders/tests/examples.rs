@@ -0,0 +1,3 @@
+use boiling_boulders::{parse, part_one, part_two};
+
+aoc::examples!(parse, part_one, part_two);
@@ -0,0 +1,625 @@
+//! Day 19: Not Enough Minerals.
+
+use std::{
+    fmt::Display,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+};
+
+use aoc::{Input, InputError};
+use eyre::eyre;
+use log::info;
+use regex::Regex;
+
+/// Index into `Blueprint::resources`. Every robot collects exactly one
+/// resource, so robots are identified by that resource too.
+type Resource = usize;
+
+/// What a robot costs, in units of each resource.
+#[derive(Clone, Debug)]
+struct Recipe {
+    robot: Resource,
+    cost: Vec<i32>,
+}
+
+/// One robot economy: the resources in play and the recipes for the robots
+/// that collect them. Any robot may cost any combination of resources.
+#[derive(Debug)]
+pub struct Blueprint {
+    index: i32,
+    resources: Vec<String>,
+    recipes: Vec<Recipe>,
+}
+
+/// What the factory starts with and what it's trying to make the most of.
+/// Together with a `Blueprint` this is everything the solver knows.
+#[derive(Clone, Debug)]
+pub struct Rules {
+    pub target: String,
+    pub starting_robots: Vec<String>,
+}
+
+impl Rules {
+    /// Day 19: one ore robot to begin with, crack as many geodes as possible.
+    pub fn day19() -> Rules {
+        Rules {
+            target: "geode".to_owned(),
+            starting_robots: vec!["ore".to_owned()],
+        }
+    }
+}
+
+/// A blueprint and rules resolved against each other, ready to search.
+pub struct Economy<'a> {
+    blueprint: &'a Blueprint,
+    target: Resource,
+    starting_robots: Vec<i32>,
+    max_useful_robots: Vec<i32>,
+}
+
+impl<'a> Economy<'a> {
+    pub fn new(blueprint: &'a Blueprint, rules: &Rules) -> eyre::Result<Economy<'a>> {
+        let resource = |name: &str| {
+            blueprint
+                .resources
+                .iter()
+                .position(|r| r == name)
+                .ok_or_else(|| eyre!("Blueprint {} has no {} robot", blueprint.index, name))
+        };
+
+        let target = resource(&rules.target)?;
+        let mut starting_robots = vec![0; blueprint.resources.len()];
+        for name in rules.starting_robots.iter() {
+            starting_robots[resource(name)?] += 1;
+        }
+
+        // Only one robot can be built per minute, so once a resource is
+        // collected as fast as the most expensive recipe can spend it another
+        // robot for it is wasted. The target is never surplus.
+        let mut max_useful_robots = vec![0; blueprint.resources.len()];
+        for recipe in blueprint.recipes.iter() {
+            for (max, cost) in max_useful_robots.iter_mut().zip(recipe.cost.iter()) {
+                *max = std::cmp::max(*max, *cost);
+            }
+        }
+        max_useful_robots[target] = i32::MAX;
+
+        Ok(Economy {
+            blueprint,
+            target,
+            starting_robots,
+            max_useful_robots,
+        })
+    }
+
+    /// The blueprint's number.
+    pub fn index(&self) -> i32 {
+        self.blueprint.index
+    }
+
+    /// Minutes until `recipe` is built when saving up for nothing else,
+    /// counting the minute spent building it, or `None` if the robots needed
+    /// to ever afford it don't exist yet.
+    fn minutes_to_build(&self, recipe: &Recipe, state: &State) -> Option<i32> {
+        let wait = recipe
+            .cost
+            .iter()
+            .zip(state.stock.iter().zip(state.robots.iter()))
+            .map(|(&cost, (&stock, &robots))| {
+                if stock >= cost {
+                    Some(0)
+                } else if robots == 0 {
+                    None
+                } else {
+                    Some((cost - stock + robots - 1) / robots)
+                }
+            })
+            .try_fold(0, |wait, resource_wait| {
+                Some(std::cmp::max(wait, resource_wait?))
+            })?;
+        Some(wait + 1)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct State {
+    stock: Vec<i32>,
+    robots: Vec<i32>,
+}
+
+impl State {
+    fn new(economy: &Economy) -> State {
+        State {
+            stock: vec![0; economy.blueprint.resources.len()],
+            robots: economy.starting_robots.clone(),
+        }
+    }
+
+    fn collect(&mut self, minutes: i32) {
+        for (stock, robots) in self.stock.iter_mut().zip(self.robots.iter()) {
+            *stock += robots * minutes;
+        }
+    }
+
+    /// Collects for `minutes`, paying for the robot out of the stock and
+    /// putting it to work at the end of the last minute.
+    fn build(&self, recipe: &Recipe, minutes: i32) -> State {
+        let mut next = self.clone();
+        next.collect(minutes);
+        for (stock, cost) in next.stock.iter_mut().zip(recipe.cost.iter()) {
+            *stock -= cost;
+        }
+        next.robots[recipe.robot] += 1;
+        next
+    }
+
+    /// How much of `resource` there is at the end if no more robots are built.
+    fn idle_total(&self, resource: Resource, minutes_left: i32) -> i32 {
+        self.stock[resource] + self.robots[resource] * minutes_left
+    }
+}
+
+/// Never less than the true best: a new target robot every remaining minute
+/// adds `minutes_left - 1 + minutes_left - 2 + ... + 0` on top of idling.
+fn upper_bound(economy: &Economy, state: &State, minutes_left: i32) -> i32 {
+    state.idle_total(economy.target, minutes_left) + minutes_left * (minutes_left - 1) / 2
+}
+
+/// The most of the target found in the given minutes, and the robots built
+/// to get it as (minute, robot) pairs with minutes counted from 1.
+#[derive(Clone, Debug, Default)]
+pub struct Plan {
+    pub amount: i32,
+    minutes: i32,
+    builds: Vec<(i32, Resource)>,
+}
+
+impl Plan {
+    /// The minute each robot is started, and what it collects.
+    pub fn builds<'a>(&self, economy: &'a Economy) -> Vec<(i32, &'a str)> {
+        let resources = &economy.blueprint.resources;
+        self.builds
+            .iter()
+            .map(|&(minute, robot)| (minute, resources[robot].as_str()))
+            .collect()
+    }
+
+    /// The plan replayed minute by minute.
+    pub fn trace<'a>(&'a self, economy: &'a Economy<'a>) -> Trace<'a> {
+        Trace {
+            economy,
+            plan: self,
+        }
+    }
+}
+
+/// Depth first over the choice of which robot to build next, jumping straight
+/// to the minute it's finished. Waiting only ever pays off as saving up for a
+/// particular robot, or as idling out the clock, so every build order is
+/// covered and the result is exact. `builds` holds the minutes left after each
+/// build so far.
+fn find_most_from(
+    economy: &Economy,
+    state: State,
+    minutes_left: i32,
+    builds: &mut Vec<(i32, Resource)>,
+    best: &mut Plan,
+) {
+    let idle_total = state.idle_total(economy.target, minutes_left);
+    if idle_total > best.amount {
+        best.amount = idle_total;
+        best.builds = builds.clone();
+    }
+    if upper_bound(economy, &state, minutes_left) <= best.amount {
+        return;
+    }
+
+    // Trying the target robot first finds good answers early, which makes the
+    // bound bite sooner.
+    let recipes = economy.blueprint.recipes.iter();
+    let (target, others): (Vec<&Recipe>, Vec<&Recipe>) =
+        recipes.partition(|recipe| recipe.robot == economy.target);
+    for recipe in target.into_iter().chain(others.into_iter().rev()) {
+        if state.robots[recipe.robot] >= economy.max_useful_robots[recipe.robot] {
+            continue;
+        }
+        // A robot finished in the last minute never gets to collect anything.
+        if let Some(minutes) = economy.minutes_to_build(recipe, &state) {
+            if minutes < minutes_left {
+                let next = state.build(recipe, minutes);
+                builds.push((minutes_left - minutes, recipe.robot));
+                find_most_from(economy, next, minutes_left - minutes, builds, best);
+                builds.pop();
+            }
+        }
+    }
+}
+
+fn find_most(economy: &Economy, minutes: i32) -> Plan {
+    let mut best = Plan {
+        minutes,
+        ..Plan::default()
+    };
+    find_most_from(
+        economy,
+        State::new(economy),
+        minutes,
+        &mut Vec::new(),
+        &mut best,
+    );
+    for (minute, _robot) in best.builds.iter_mut() {
+        *minute = minutes - *minute;
+    }
+    best
+}
+
+/// Replays a `Plan` minute by minute in the style of the puzzle's walkthrough.
+pub struct Trace<'a> {
+    economy: &'a Economy<'a>,
+    plan: &'a Plan,
+}
+
+impl Display for Trace<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let resources = &self.economy.blueprint.resources;
+        let mut state = State::new(self.economy);
+        for minute in 1..=self.plan.minutes {
+            writeln!(f, "== Minute {} ==", minute)?;
+
+            let recipe = self
+                .plan
+                .builds
+                .iter()
+                .find(|(build_minute, _robot)| *build_minute == minute)
+                .map(|(_minute, robot)| &self.economy.blueprint.recipes[*robot]);
+            if let Some(recipe) = recipe {
+                let spent: Vec<String> = recipe
+                    .cost
+                    .iter()
+                    .enumerate()
+                    .filter(|(_resource, cost)| **cost > 0)
+                    .map(|(resource, cost)| format!("{} {}", cost, resources[resource]))
+                    .collect();
+                writeln!(
+                    f,
+                    "Spend {} to start building a {}-collecting robot.",
+                    spent.join(" and "),
+                    resources[recipe.robot]
+                )?;
+                for (stock, cost) in state.stock.iter_mut().zip(recipe.cost.iter()) {
+                    *stock -= cost;
+                }
+            }
+
+            for (resource, robots) in state.robots.iter().enumerate() {
+                if *robots == 0 {
+                    continue;
+                }
+                state.stock[resource] += robots;
+                writeln!(
+                    f,
+                    "{} {}-collecting robot{} {} {} {}; you now have {} {}.",
+                    robots,
+                    resources[resource],
+                    if *robots == 1 { "" } else { "s" },
+                    if *robots == 1 { "collects" } else { "collect" },
+                    robots,
+                    resources[resource],
+                    state.stock[resource],
+                    resources[resource]
+                )?;
+            }
+
+            if let Some(recipe) = recipe {
+                state.robots[recipe.robot] += 1;
+                writeln!(
+                    f,
+                    "The new {}-collecting robot is ready; you now have {} of them.",
+                    resources[recipe.robot], state.robots[recipe.robot]
+                )?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Spreads the blueprints over `jobs` scoped threads, logging a progress line
+/// as each one finishes. Results come back in blueprint order regardless of
+/// which thread got there first.
+pub fn find_most_in_parallel(economies: &[Economy], minutes: i32, jobs: usize) -> Vec<Plan> {
+    let next_economy = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    std::thread::scope(|scope| {
+        for _ in 0..std::cmp::max(jobs, 1) {
+            let sender = sender.clone();
+            let next_economy = &next_economy;
+            scope.spawn(move || loop {
+                let i = next_economy.fetch_add(1, Ordering::Relaxed);
+                let Some(economy) = economies.get(i) else {
+                    break;
+                };
+                sender.send((i, find_most(economy, minutes))).unwrap();
+            });
+        }
+        drop(sender);
+
+        let mut most = vec![Plan::default(); economies.len()];
+        for (finished, (i, plan)) in receiver.iter().enumerate() {
+            let economy = &economies[i];
+            info!(
+                "[{}/{}] Blueprint {} can make {} {}",
+                finished + 1,
+                economies.len(),
+                economy.blueprint.index,
+                plan.amount,
+                economy.blueprint.resources[economy.target]
+            );
+            most[i] = plan;
+        }
+        most
+    })
+}
+
+/// The sum of every blueprint's quality level, from its best 24 minute plan.
+pub fn total_quality(economies: &[Economy], plans: &[Plan]) -> i32 {
+    economies
+        .iter()
+        .zip(plans.iter())
+        .map(|(economy, plan)| plan.amount * economy.blueprint.index)
+        .sum()
+}
+
+/// The product of the most each plan makes.
+pub fn product(plans: &[Plan]) -> usize {
+    plans.iter().map(|plan| plan.amount as usize).product()
+}
+
+#[derive(Debug)]
+enum ParseErrorKind {
+    UnexpectedText(String),
+    RecipeOutsideBlueprint,
+    BadCost(String),
+    UnknownResource { robot: String, resource: String },
+    DuplicateRobot(String),
+    BadIndex(String),
+    MissingRobot(String),
+}
+
+#[derive(Debug)]
+struct ParseError {
+    line: usize,
+    kind: ParseErrorKind,
+}
+
+impl Display for ParseErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseErrorKind::UnexpectedText(text) => write!(f, "unexpected text {:?}", text),
+            ParseErrorKind::RecipeOutsideBlueprint => {
+                write!(f, "robot recipe before any \"Blueprint N:\" header")
+            }
+            ParseErrorKind::BadCost(cost) => write!(f, "can't read cost {:?}", cost),
+            ParseErrorKind::UnknownResource { robot, resource } => write!(
+                f,
+                "{} robot costs {}, but no robot collects {}",
+                robot, resource, resource
+            ),
+            ParseErrorKind::DuplicateRobot(robot) => {
+                write!(f, "{} robot recipe given twice", robot)
+            }
+            ParseErrorKind::BadIndex(index) => write!(f, "can't read blueprint number {:?}", index),
+            ParseErrorKind::MissingRobot(robot) => {
+                write!(f, "blueprint has no {} robot recipe", robot)
+            }
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.kind)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A recipe as written, with the line it came from.
+struct PartialRecipe {
+    line: usize,
+    robot: String,
+    cost: Vec<(i32, String)>,
+}
+
+/// A blueprint while its recipes are still being read. Costs can only be
+/// resolved to resource indices once every robot is known.
+struct PartialBlueprint {
+    line: usize,
+    index: i32,
+    recipes: Vec<PartialRecipe>,
+}
+
+impl PartialBlueprint {
+    fn new(line: usize, index: i32) -> PartialBlueprint {
+        PartialBlueprint {
+            line,
+            index,
+            recipes: Vec::new(),
+        }
+    }
+
+    fn add_recipe(&mut self, line: usize, robot: &str, cost: &str) -> Result<(), ParseError> {
+        let error = |kind| ParseError { line, kind };
+
+        if self.recipes.iter().any(|recipe| recipe.robot == robot) {
+            return Err(error(ParseErrorKind::DuplicateRobot(robot.to_owned())));
+        }
+
+        let cost = cost
+            .split(" and ")
+            .map(|amount| {
+                let (count, resource) = amount
+                    .trim()
+                    .split_once(char::is_whitespace)
+                    .ok_or_else(|| error(ParseErrorKind::BadCost(amount.to_owned())))?;
+                let count = count
+                    .parse::<i32>()
+                    .map_err(|_| error(ParseErrorKind::BadCost(amount.to_owned())))?;
+                Ok((count, resource.trim().to_owned()))
+            })
+            .collect::<Result<Vec<_>, ParseError>>()?;
+        self.recipes.push(PartialRecipe {
+            line,
+            robot: robot.to_owned(),
+            cost,
+        });
+        Ok(())
+    }
+
+    /// Resolves the costs, and checks there's a recipe for every robot the
+    /// rules start with or are after.
+    fn finish(self, rules: &Rules) -> Result<Blueprint, ParseError> {
+        let resources: Vec<String> = self
+            .recipes
+            .iter()
+            .map(|recipe| recipe.robot.clone())
+            .collect();
+
+        let mut recipes = Vec::new();
+        for PartialRecipe { line, robot, cost } in self.recipes.iter() {
+            let mut recipe = Recipe {
+                robot: recipes.len(),
+                cost: vec![0; resources.len()],
+            };
+            for (count, resource) in cost.iter() {
+                let resource = resources
+                    .iter()
+                    .position(|r| r == resource)
+                    .ok_or_else(|| ParseError {
+                        line: *line,
+                        kind: ParseErrorKind::UnknownResource {
+                            robot: robot.clone(),
+                            resource: resource.clone(),
+                        },
+                    })?;
+                recipe.cost[resource] += count;
+            }
+            recipes.push(recipe);
+        }
+
+        let needed = std::iter::once(&rules.target).chain(rules.starting_robots.iter());
+        for robot in needed {
+            if !resources.contains(robot) {
+                return Err(ParseError {
+                    line: self.line,
+                    kind: ParseErrorKind::MissingRobot(robot.clone()),
+                });
+            }
+        }
+
+        Ok(Blueprint {
+            index: self.index,
+            resources,
+            recipes,
+        })
+    }
+}
+
+/// Reads blueprints whether each one is on a single line or spread over
+/// several, with the robot recipes in any order.
+fn parse_blueprints(s: &str, rules: &Rules) -> Result<Vec<Blueprint>, ParseError> {
+    let r = Regex::new(
+        r"Blueprint\s+(\d+)\s*:|Each\s+(\w+)\s+robot\s+costs\s+(\d+\s+\w+(?:\s+and\s+\d+\s+\w+)*)\s*\.",
+    )
+    .unwrap();
+    let line_of = |offset: usize| s[..offset].matches('\n').count() + 1;
+
+    let mut blueprints = Vec::new();
+    let mut current: Option<PartialBlueprint> = None;
+    let mut end_of_last_match = 0;
+    for captures in r.captures_iter(s) {
+        let whole = captures.get(0).unwrap();
+        let between = &s[end_of_last_match..whole.start()];
+        if !between.trim().is_empty() {
+            let skipped = between.len() - between.trim_start().len();
+            return Err(ParseError {
+                line: line_of(end_of_last_match + skipped),
+                kind: ParseErrorKind::UnexpectedText(between.trim().to_owned()),
+            });
+        }
+        end_of_last_match = whole.end();
+
+        let line = line_of(whole.start());
+        if let Some(index) = captures.get(1) {
+            if let Some(blueprint) = current.take() {
+                blueprints.push(blueprint.finish(rules)?);
+            }
+            let index = index.as_str().parse().map_err(|_| ParseError {
+                line,
+                kind: ParseErrorKind::BadIndex(index.as_str().to_owned()),
+            })?;
+            current = Some(PartialBlueprint::new(line, index));
+        } else {
+            let cost = captures[3].split_whitespace().collect::<Vec<_>>().join(" ");
+            current
+                .as_mut()
+                .ok_or(ParseError {
+                    line,
+                    kind: ParseErrorKind::RecipeOutsideBlueprint,
+                })?
+                .add_recipe(line, &captures[2], &cost)?;
+        }
+    }
+
+    let rest = &s[end_of_last_match..];
+    if !rest.trim().is_empty() {
+        let skipped = rest.len() - rest.trim_start().len();
+        return Err(ParseError {
+            line: line_of(end_of_last_match + skipped),
+            kind: ParseErrorKind::UnexpectedText(rest.trim().to_owned()),
+        });
+    }
+    if let Some(blueprint) = current.take() {
+        blueprints.push(blueprint.finish(rules)?);
+    }
+    Ok(blueprints)
+}
+
+/// Blueprints with a recipe for every robot `rules` needs.
+pub fn parse_with_rules(input: &Input, rules: &Rules) -> Result<Vec<Blueprint>, InputError> {
+    parse_blueprints(input.text(), rules).map_err(|e| input.error(e.line, e.kind))
+}
+
+/// Blueprints with ore and geode robot recipes.
+pub fn parse(input: &Input) -> Result<Vec<Blueprint>, InputError> {
+    parse_with_rules(input, &Rules::day19())
+}
+
+fn day19_economies(blueprints: &[Blueprint]) -> Vec<Economy<'_>> {
+    blueprints
+        .iter()
+        .map(|blueprint| Economy::new(blueprint, &Rules::day19()))
+        .collect::<eyre::Result<Vec<_>>>()
+        .expect("parse checks every blueprint has ore and geode robots")
+}
+
+fn all_cores() -> usize {
+    std::thread::available_parallelism().map_or(1, usize::from)
+}
+
+pub fn part_one(blueprints: &[Blueprint]) -> i32 {
+    let economies = day19_economies(blueprints);
+    total_quality(
+        &economies,
+        &find_most_in_parallel(&economies, 24, all_cores()),
+    )
+}
+
+/// The product of the most the first three blueprints can make in 32 minutes.
+pub fn part_two(blueprints: &[Blueprint]) -> usize {
+    let economies = day19_economies(&blueprints[..std::cmp::min(3, blueprints.len())]);
+    product(&find_most_in_parallel(&economies, 32, all_cores()))
+}
@@ -0,0 +1,81 @@
+use aoc::{Input, InputError};
+use not_enough_minerals::parse;
+
+fn parse_error(text: &str) -> InputError {
+    match parse(&Input::new("bad.txt", text)) {
+        Ok(_) => panic!("{text:?} parsed"),
+        Err(e) => e,
+    }
+}
+
+#[test]
+fn unexpected_text_is_reported_on_its_line() {
+    let e = parse_error(
+        "Blueprint 1:\n  Each ore robot costs 4 ore.\n  Each clay robot costs two ore.\n",
+    );
+    assert_eq!(e.line, 3);
+    assert_eq!(
+        e.message,
+        "unexpected text \"Each clay robot costs two ore.\""
+    );
+}
+
+#[test]
+fn trailing_text_is_reported_on_its_line() {
+    let e = parse_error("Blueprint 1: Each ore robot costs 4 ore.\n\nand then some\n");
+    assert_eq!(e.line, 3);
+    assert_eq!(e.message, "unexpected text \"and then some\"");
+}
+
+#[test]
+fn recipes_need_a_blueprint() {
+    let e = parse_error("Each ore robot costs 4 ore.\n");
+    assert_eq!(e.line, 1);
+    assert_eq!(e.message, "robot recipe before any \"Blueprint N:\" header");
+}
+
+#[test]
+fn costs_must_be_collected_by_a_robot() {
+    let e = parse_error(
+        "Blueprint 1:\n  Each ore robot costs 4 ore.\n  Each clay robot costs 2 ore and 1 sand.\n",
+    );
+    assert_eq!(e.line, 3);
+    assert_eq!(
+        e.message,
+        "clay robot costs sand, but no robot collects sand"
+    );
+}
+
+#[test]
+fn robots_are_only_given_once() {
+    let e = parse_error(
+        "Blueprint 1: Each ore robot costs 4 ore. Each geode robot costs 2 ore.\nBlueprint 2: Each ore robot costs 4 ore.\n  Each ore robot costs 2 ore.\n",
+    );
+    assert_eq!(e.line, 3);
+    assert_eq!(e.message, "ore robot recipe given twice");
+    assert_eq!(e.to_string(), "bad.txt:3: ore robot recipe given twice");
+}
+
+#[test]
+fn blueprints_need_ore_and_geode_robots() {
+    let e = parse_error(
+        "Blueprint 1:\n  Each ore robot costs 4 ore.\n  Each geode robot costs 2 ore.\n\n\
+         Blueprint 2:\n  Each ore robot costs 2 ore.\n  Each clay robot costs 3 ore.\n",
+    );
+    assert_eq!(e.line, 5);
+    assert_eq!(e.message, "blueprint has no geode robot recipe");
+
+    let e = parse_error("Blueprint 1: Each geode robot costs 2 geode.\n");
+    assert_eq!(e.line, 1);
+    assert_eq!(e.message, "blueprint has no ore robot recipe");
+}
+
+#[test]
+fn blueprint_numbers_have_to_fit() {
+    let e = parse_error(
+        "Blueprint 1: Each ore robot costs 4 ore. Each geode robot costs 2 ore.\n\
+         Blueprint 99999999999: Each ore robot costs 4 ore.\n",
+    );
+    assert_eq!(e.line, 2);
+    assert_eq!(e.message, "can't read blueprint number \"99999999999\"");
+}
@@ -0,0 +1,3 @@
+use not_enough_minerals::{parse, part_one, part_two};
+
+aoc::examples!(parse, part_one, part_two);
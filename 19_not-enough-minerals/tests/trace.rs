@@ -0,0 +1,40 @@
+use aoc::Input;
+use not_enough_minerals::{find_most_in_parallel, parse, Economy, Rules};
+
+#[test]
+fn blueprint_one_follows_the_puzzle_walkthrough() {
+    let blueprints = parse(&Input::new(
+        "example.txt",
+        include_str!("../examples/example.txt"),
+    ))
+    .unwrap();
+    let economy = Economy::new(&blueprints[0], &Rules::day19()).unwrap();
+    let plans = find_most_in_parallel(std::slice::from_ref(&economy), 24, 1);
+    let plan = &plans[0];
+
+    assert_eq!(plan.amount, 9);
+    assert_eq!(
+        plan.builds(&economy),
+        [
+            (3, "clay"),
+            (5, "clay"),
+            (7, "clay"),
+            (11, "obsidian"),
+            (12, "clay"),
+            (15, "obsidian"),
+            (18, "geode"),
+            (21, "geode"),
+        ]
+    );
+
+    let trace = plan.trace(&economy).to_string();
+    let minute_24 = &trace[trace.find("== Minute 24 ==").unwrap()..];
+    assert_eq!(
+        minute_24,
+        "== Minute 24 ==\n\
+         1 ore-collecting robot collects 1 ore; you now have 6 ore.\n\
+         4 clay-collecting robots collect 4 clay; you now have 41 clay.\n\
+         2 obsidian-collecting robots collect 2 obsidian; you now have 8 obsidian.\n\
+         2 geode-collecting robots collect 2 geode; you now have 9 geode.\n\n"
+    );
+}
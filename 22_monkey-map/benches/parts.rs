@@ -0,0 +1,16 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use monkey_map::{parse, part_one, part_two};
+
+fn parts(c: &mut Criterion) {
+    aoc::bench::parts(
+        c,
+        "22_monkey-map",
+        include_str!("../input.txt"),
+        parse,
+        part_one,
+        part_two,
+    );
+}
+
+criterion_group!(benches, parts);
+criterion_main!(benches);
@@ -0,0 +1,1245 @@
+//! Day 22: Monkey Map.
+
+#[cfg(feature = "test-util")]
+pub mod nets;
+
+use aoc::{
+    parse::{alt, char_if, map, number, PResult},
+    Input, InputError,
+};
+use eyre::eyre;
+use log::{debug, trace};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{Display, Write},
+    ops::Range,
+};
+
+/// A unit axis vector in cube space; x points Right, y points Back and z
+/// points Top.
+type Vector = [i32; 3];
+
+fn negate(v: Vector) -> Vector {
+    [-v[0], -v[1], -v[2]]
+}
+
+/// Where a face of the net ends up once folded: its outward normal, and the
+/// directions the map's rightward and downward axes point in on that face.
+#[derive(Copy, Clone, Debug)]
+struct Orientation {
+    normal: Vector,
+    right: Vector,
+    down: Vector,
+}
+
+impl Orientation {
+    /// The face the walk starts on lies on top, with the map's right pointing
+    /// at the Right face and the map's down pointing at the Front face.
+    fn top() -> Orientation {
+        Orientation {
+            normal: [0, 0, 1],
+            right: [1, 0, 0],
+            down: [0, -1, 0],
+        }
+    }
+
+    /// Rotate the cube a quarter turn so that the face adjacent on the given
+    /// side of the net becomes the one facing out.
+    fn fold(&self, facing: Facing) -> Orientation {
+        match facing {
+            Facing::Right => Orientation {
+                normal: self.right,
+                right: negate(self.normal),
+                down: self.down,
+            },
+            Facing::Left => Orientation {
+                normal: negate(self.right),
+                right: self.normal,
+                down: self.down,
+            },
+            Facing::Down => Orientation {
+                normal: self.down,
+                right: self.right,
+                down: negate(self.normal),
+            },
+            Facing::Up => Orientation {
+                normal: negate(self.down),
+                right: self.right,
+                down: self.normal,
+            },
+        }
+    }
+
+    /// The outward normal of the face across the edge on the given side.
+    fn towards(&self, facing: Facing) -> Vector {
+        match facing {
+            Facing::Right => self.right,
+            Facing::Down => self.down,
+            Facing::Left => negate(self.right),
+            Facing::Up => negate(self.down),
+        }
+    }
+}
+
+/// The non-blank cells of the net.
+fn tile_count(spots: &Spots) -> usize {
+    spots.iter().flatten().filter(|&&spot| spot != ' ').count()
+}
+
+/// Fold the net into a cube: infer the face size from the number of tiles,
+/// flood-fill the faces from the top-left one and orient each face by
+/// rotating the cube across the edge it was reached through.
+fn parse_cube(map: &Map) -> eyre::Result<(Vec<CubeFace>, usize)> {
+    let tiles = tile_count(&map.spots);
+    let stride = (1..).find(|s| 6 * s * s >= tiles).unwrap();
+    if 6 * stride * stride != tiles {
+        return Err(eyre!("{tiles} tiles can't be split into six square faces"));
+    }
+
+    let start_col = map
+        .spots
+        .first()
+        .and_then(|row| row.iter().position(|&spot| spot != ' '))
+        .ok_or(eyre!("The map is empty"))?;
+    if start_col % stride != 0 {
+        return Err(eyre!(
+            "The first face starts at column {start_col}, which isn't a multiple of {stride}"
+        ));
+    }
+
+    let mut orientations = BTreeMap::from([((0, start_col), Orientation::top())]);
+    let mut queue = vec![(0, start_col)];
+    while let Some((row, col)) = queue.pop() {
+        let orientation = orientations[&(row, col)];
+        for facing in [Facing::Right, Facing::Down, Facing::Left, Facing::Up] {
+            let next = match facing {
+                Facing::Right => (row, col + stride),
+                Facing::Down => (row + stride, col),
+                Facing::Left if col >= stride => (row, col - stride),
+                Facing::Up if row >= stride => (row - stride, col),
+                _ => continue,
+            };
+            if map.is_tile(next.0, next.1) && !orientations.contains_key(&next) {
+                orientations.insert(next, orientation.fold(facing));
+                queue.push(next);
+            }
+        }
+    }
+
+    if orientations.len() != 6 {
+        return Err(eyre!(
+            "Found {} connected faces of size {stride}, expected 6",
+            orientations.len()
+        ));
+    }
+
+    for &(row, col) in orientations.keys() {
+        if !(row..row + stride).all(|r| (col..col + stride).all(|c| map.is_tile(r, c))) {
+            return Err(eyre!(
+                "The face at row={row}, col={col} isn't a full {stride}x{stride} square"
+            ));
+        }
+    }
+
+    let cubefaces: Vec<CubeFace> = orientations
+        .iter()
+        .map(|(&(row, col), orientation)| CubeFace::new(stride, row, col, orientation))
+        .collect();
+
+    let faces: BTreeSet<Face> = cubefaces.iter().map(|cubeface| cubeface.face).collect();
+    if faces.len() != 6 {
+        return Err(eyre!("The net overlaps itself when folded"));
+    }
+
+    Ok((cubefaces, stride))
+}
+
+/// ```text
+///      ____T0____
+///    /|         /|
+///  T3 |       T1 |
+///  /  V3      /  V0
+/// /____T2____/   |
+/// |   |     |    |
+/// |   |___B2|____|
+/// V2  /     V1  /
+/// | B3      | B1
+/// |/___B0___|/
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+enum Edge {
+    T0,
+    T1,
+    T2,
+    T3,
+    V0,
+    V1,
+    V2,
+    V3,
+    B0,
+    B1,
+    B2,
+    B3,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+enum Face {
+    Top,
+    Bottom,
+    Left,
+    Right,
+    Front,
+    Back,
+}
+
+impl Face {
+    fn from_normal(normal: Vector) -> Face {
+        match normal {
+            [0, 0, 1] => Face::Top,
+            [0, 0, -1] => Face::Bottom,
+            [-1, 0, 0] => Face::Left,
+            [1, 0, 0] => Face::Right,
+            [0, -1, 0] => Face::Front,
+            [0, 1, 0] => Face::Back,
+            _ => panic!("{normal:?} isn't a unit axis vector"),
+        }
+    }
+}
+
+impl Edge {
+    /// The edge where two neighbouring faces meet.
+    fn between(a: Face, b: Face) -> Edge {
+        match (a.min(b), a.max(b)) {
+            (Face::Top, Face::Back) => Edge::T0,
+            (Face::Top, Face::Right) => Edge::T1,
+            (Face::Top, Face::Front) => Edge::T2,
+            (Face::Top, Face::Left) => Edge::T3,
+            (Face::Right, Face::Back) => Edge::V0,
+            (Face::Right, Face::Front) => Edge::V1,
+            (Face::Left, Face::Front) => Edge::V2,
+            (Face::Left, Face::Back) => Edge::V3,
+            (Face::Bottom, Face::Front) => Edge::B0,
+            (Face::Bottom, Face::Right) => Edge::B1,
+            (Face::Bottom, Face::Back) => Edge::B2,
+            (Face::Bottom, Face::Left) => Edge::B3,
+            (a, b) => panic!("{a:?} and {b:?} don't share an edge"),
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+struct CubeFace {
+    col: usize,
+    row: usize,
+    stride: usize,
+    face: Face,
+    orientation: Orientation,
+    edges: [Edge; 4],
+}
+
+impl CubeFace {
+    fn new(stride: usize, row: usize, col: usize, orientation: &Orientation) -> CubeFace {
+        let face = Face::from_normal(orientation.normal);
+        let edge = |facing| Edge::between(face, Face::from_normal(orientation.towards(facing)));
+        CubeFace {
+            col,
+            row,
+            stride,
+            face,
+            orientation: *orientation,
+            edges: [
+                edge(Facing::Up),
+                edge(Facing::Right),
+                edge(Facing::Down),
+                edge(Facing::Left),
+            ],
+        }
+    }
+
+    fn top(&self) -> Edge {
+        self.edges[0]
+    }
+    fn right(&self) -> Edge {
+        self.edges[1]
+    }
+    fn bottom(&self) -> Edge {
+        self.edges[2]
+    }
+    fn left(&self) -> Edge {
+        self.edges[3]
+    }
+
+    fn coming_from(&self, edge: Edge, from_left_edge: usize) -> (usize, usize, Face, Facing) {
+        trace!("{from_left_edge} on {edge:?}");
+        let from_right_edge = self.stride - from_left_edge - 1;
+        if edge == self.top() {
+            (
+                self.col + from_right_edge,
+                self.row,
+                self.face,
+                Facing::Down,
+            )
+        } else if edge == self.right() {
+            (
+                self.col + self.stride - 1,
+                self.row + from_right_edge,
+                self.face,
+                Facing::Left,
+            )
+        } else if edge == self.bottom() {
+            (
+                self.col + from_left_edge,  //
+                self.row + self.stride - 1, //
+                self.face,                  //
+                Facing::Up,                 //
+            )
+        } else if edge == self.left() {
+            (
+                self.col,
+                self.row + from_left_edge,
+                self.face,
+                Facing::Right,
+            )
+        } else {
+            panic!(
+                "Asked to come from {edge:?} that face {:?} doesn't have!",
+                self.face
+            );
+        }
+    }
+}
+
+impl Display for CubeFace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_fmt(format_args!(
+            "col={}, row={} [top: {:?}, right: {:?}, bottom: {:?}, left: {:?}]  {:?}",
+            self.col,
+            self.row,
+            self.top(),
+            self.right(),
+            self.bottom(),
+            self.left(),
+            self.face,
+        ))
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+enum Facing {
+    Right,
+    Down,
+    Left,
+    Up,
+}
+
+impl Facing {
+    fn to_char(self) -> char {
+        match self {
+            Facing::Right => '>',
+            Facing::Down => 'v',
+            Facing::Left => '<',
+            Facing::Up => '^',
+        }
+    }
+
+    fn to_usize(self) -> usize {
+        match self {
+            Facing::Right => 0,
+            Facing::Down => 1,
+            Facing::Left => 2,
+            Facing::Up => 3,
+        }
+    }
+
+    fn turn_right(&self) -> Facing {
+        match self {
+            Facing::Right => Facing::Down,
+            Facing::Down => Facing::Left,
+            Facing::Left => Facing::Up,
+            Facing::Up => Facing::Right,
+        }
+    }
+
+    fn turn_left(&self) -> Facing {
+        match self {
+            Facing::Right => Facing::Up,
+            Facing::Down => Facing::Right,
+            Facing::Left => Facing::Down,
+            Facing::Up => Facing::Left,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub enum Instruction {
+    Advance(usize),
+    TurnLeft,
+    TurnRight,
+}
+
+/// Numbers of tiles to move forward, and `L` or `R` to turn, run together.
+fn path(mut input: &str) -> PResult<'_, Vec<Instruction>> {
+    let instruction = alt((
+        map(
+            char_if("a number, L or R", |c| c == 'L' || c == 'R'),
+            |c| match c {
+                'L' => Instruction::TurnLeft,
+                _ => Instruction::TurnRight,
+            },
+        ),
+        map(number(), Instruction::Advance),
+    ));
+
+    let mut instructions = Vec::new();
+    while !input.is_empty() {
+        let (rest, next) = instruction(input)?;
+        instructions.push(next);
+        input = rest;
+    }
+    Ok((input, instructions))
+}
+
+type Spots = Vec<Vec<char>>;
+
+#[derive(Clone)]
+pub struct Map {
+    spots: Spots,
+}
+
+impl Display for Map {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.spots.iter() {
+            for c in row.iter() {
+                f.write_char(*c)?;
+            }
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+impl Map {
+    fn new(mut spots: Spots) -> Map {
+        let w = spots
+            .iter()
+            .fold(0, |max, row| std::cmp::max(max, row.len()));
+
+        spots.iter_mut().for_each(|row| row.resize(w, ' '));
+
+        Map { spots }
+    }
+
+    fn is_tile(&self, row: usize, col: usize) -> bool {
+        self.spots
+            .get(row)
+            .and_then(|spots| spots.get(col))
+            .is_some_and(|&spot| spot != ' ')
+    }
+}
+
+/// How the character moves, and where it ends up when it walks off the edge
+/// of a face or of the map.
+pub trait WrapRule {
+    /// Where one step forward takes the character, walls or not.
+    fn next(&self, character: &Character) -> Option<Character>;
+}
+
+/// Walking off the map comes back in on the opposite side of the same row
+/// or column.
+pub struct FlatWrap {
+    // For a given column, the first and last+1 row indexes that are valid
+    col_ranges: Vec<Range<usize>>,
+    // For a given row, the first and last+1 column indexes that are valid
+    row_ranges: Vec<Range<usize>>,
+}
+
+impl FlatWrap {
+    pub fn new(map: &Map) -> FlatWrap {
+        let spots = &map.spots;
+        let w = spots.first().map_or(0, |row| row.len());
+
+        let row_ranges = spots
+            .iter()
+            .map(|row| {
+                let start = row.iter().position(|spot| *spot != ' ').unwrap();
+                let end = row.iter().rev().position(|spot| *spot != ' ').unwrap();
+                start..(w - end)
+            })
+            .collect();
+
+        let col_ranges = (0..w)
+            .map(|col| {
+                let start = spots.iter().position(|row| row[col] != ' ').unwrap();
+                let len = spots[start..]
+                    .iter()
+                    .take_while(|row| row[col] != ' ')
+                    .count();
+                start..start + len
+            })
+            .collect();
+
+        FlatWrap {
+            col_ranges,
+            row_ranges,
+        }
+    }
+}
+
+impl WrapRule for FlatWrap {
+    fn next(&self, character: &Character) -> Option<Character> {
+        // first valid column in this row
+        let col_start = self.row_ranges[character.row].start;
+        // last+1 valid column in this row
+        let col_end = self.row_ranges[character.row].end;
+        // first valid row in this column
+        let row_start = self.col_ranges[character.col].start;
+        // last+1 valid row in this column
+        let row_end = self.col_ranges[character.col].end;
+
+        let (next_row, next_col) = match character.facing {
+            Facing::Right => {
+                if character.col + 1 == col_end {
+                    (character.row, col_start)
+                } else {
+                    (character.row, character.col + 1)
+                }
+            }
+            Facing::Down => {
+                if character.row + 1 == row_end {
+                    (row_start, character.col)
+                } else {
+                    (character.row + 1, character.col)
+                }
+            }
+            Facing::Left => {
+                if character.col == col_start {
+                    (character.row, col_end - 1)
+                } else {
+                    (character.row, character.col - 1)
+                }
+            }
+            Facing::Up => {
+                if character.row == row_start {
+                    (row_end - 1, character.col)
+                } else {
+                    (character.row - 1, character.col)
+                }
+            }
+        };
+
+        Some(Character {
+            row: next_row,
+            col: next_col,
+            ..*character
+        })
+    }
+}
+
+/// The map is folded up into a cube, and walking off a face continues onto
+/// the face it's folded against.
+pub struct CubeWrap {
+    faces: Vec<CubeFace>,
+    stride: usize,
+}
+
+impl CubeWrap {
+    pub fn new(map: &Map) -> eyre::Result<CubeWrap> {
+        let (faces, stride) = parse_cube(map)?;
+        Ok(CubeWrap { faces, stride })
+    }
+}
+
+impl WrapRule for CubeWrap {
+    fn next(&self, character: &Character) -> Option<Character> {
+        let current_face = self
+            .faces
+            .iter()
+            .find(|cubeface| cubeface.face == character.face)?;
+
+        let (next_col, next_row, next_face, next_facing) = match character.facing {
+            Facing::Right => {
+                if character.col + 1 == current_face.col + self.stride {
+                    let adjacent_face = self.faces.iter().find(|&cubeface| {
+                        cubeface.face != character.face
+                            && cubeface.edges.contains(&current_face.right())
+                    })?;
+                    let from_left_edge = character.row - current_face.row;
+                    trace!(
+                        "{from_left_edge} facing right at {:?} of {:?}",
+                        current_face.right(),
+                        current_face.face
+                    );
+                    adjacent_face.coming_from(current_face.right(), from_left_edge)
+                } else {
+                    (
+                        character.col + 1,
+                        character.row,
+                        character.face,
+                        character.facing,
+                    )
+                }
+            }
+            Facing::Down => {
+                if character.row + 1 == current_face.row + self.stride {
+                    let adjacent_face = self.faces.iter().find(|&cubeface| {
+                        cubeface.face != character.face
+                            && cubeface.edges.contains(&current_face.bottom())
+                    })?;
+                    let from_left_edge = current_face.col + self.stride - 1 - character.col;
+                    trace!(
+                        "{from_left_edge} facing down at {:?} of {:?}",
+                        current_face.bottom(),
+                        current_face.face
+                    );
+                    adjacent_face.coming_from(current_face.bottom(), from_left_edge)
+                } else {
+                    (
+                        character.col,
+                        character.row + 1,
+                        character.face,
+                        character.facing,
+                    )
+                }
+            }
+            Facing::Left => {
+                if character.col == current_face.col {
+                    let adjacent_face = self.faces.iter().find(|&cubeface| {
+                        cubeface.face != character.face
+                            && cubeface.edges.contains(&current_face.left())
+                    })?;
+                    let from_left_edge = current_face.row + self.stride - 1 - character.row;
+                    trace!(
+                        "{from_left_edge} facing left at {:?} of {:?}",
+                        current_face.left(),
+                        current_face.face
+                    );
+                    adjacent_face.coming_from(current_face.left(), from_left_edge)
+                } else {
+                    (
+                        character.col - 1,
+                        character.row,
+                        character.face,
+                        character.facing,
+                    )
+                }
+            }
+            Facing::Up => {
+                if character.row == current_face.row {
+                    let adjacent_face = self.faces.iter().find(|&cubeface| {
+                        cubeface.face != character.face
+                            && cubeface.edges.contains(&current_face.top())
+                    })?;
+                    let from_left_edge = character.col - current_face.col;
+                    trace!(
+                        "{from_left_edge} facing up at {:?} of {:?}",
+                        current_face.top(),
+                        current_face.face
+                    );
+                    adjacent_face.coming_from(current_face.top(), from_left_edge)
+                } else {
+                    (
+                        character.col,
+                        character.row - 1,
+                        character.face,
+                        character.facing,
+                    )
+                }
+            }
+        };
+
+        Some(Character {
+            row: next_row,
+            col: next_col,
+            face: next_face,
+            facing: next_facing,
+        })
+    }
+}
+
+/// Where walking off a face through one side of it comes back in.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+struct Gluing {
+    face: (usize, usize),
+    side: Facing,
+    flipped: bool,
+}
+
+/// Sides of faces glued together by a config file, one gluing per line:
+///
+/// ```text
+/// size: 4
+/// 0,2 left -> 0,2 right
+/// 1,0 up -> 1,0 down flipped
+/// ```
+///
+/// Faces are named by their row and column in the net, counted in faces.
+/// Walking off the first face through the given side comes back in on the
+/// second face through its given side, and `flipped` reverses the position
+/// along the edge. Gluings work both ways, sides that touch another face in
+/// the net are glued to it already, and every other side has to be glued.
+/// The face size defaults to the one a cube would have.
+pub struct GluedWrap {
+    stride: usize,
+    gluings: BTreeMap<((usize, usize), Facing), Gluing>,
+}
+
+fn parse_side(s: &str) -> Option<Facing> {
+    match s {
+        "up" => Some(Facing::Up),
+        "down" => Some(Facing::Down),
+        "left" => Some(Facing::Left),
+        "right" => Some(Facing::Right),
+        _ => None,
+    }
+}
+
+fn parse_face(s: &str) -> Option<(usize, usize)> {
+    let (row, col) = s.split_once(',')?;
+    Some((row.trim().parse().ok()?, col.trim().parse().ok()?))
+}
+
+impl GluedWrap {
+    pub fn parse(map: &Map, s: &str) -> eyre::Result<GluedWrap> {
+        let lines: Vec<(usize, &str)> = s
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.split('#').next().unwrap().trim()))
+            .filter(|(_, line)| !line.is_empty())
+            .collect();
+
+        let stride = match lines
+            .iter()
+            .find_map(|(i, line)| Some((i, line.strip_prefix("size:")?)))
+        {
+            Some((i, size)) => size
+                .trim()
+                .parse::<usize>()
+                .map_err(|e| eyre!("line {i}: bad size: {e}"))?,
+            None => (1..).find(|s| 6 * s * s >= tile_count(&map.spots)).unwrap(),
+        };
+        if stride == 0 {
+            return Err(eyre!("The face size can't be 0"));
+        }
+
+        let is_face = |(row, col): (usize, usize)| map.is_tile(row * stride, col * stride);
+        let neighbour = |(row, col): (usize, usize), side: Facing| match side {
+            Facing::Right => Some((row, col + 1)),
+            Facing::Down => Some((row + 1, col)),
+            Facing::Left => Some((row, col.checked_sub(1)?)),
+            Facing::Up => Some((row.checked_sub(1)?, col)),
+        };
+        let is_open = |face, side| !neighbour(face, side).is_some_and(is_face);
+        let side_length = |(row, col): (usize, usize), side: Facing| {
+            let (top, left) = (row * stride, col * stride);
+            (0..stride)
+                .filter(|&along| {
+                    let (row, col) = match side {
+                        Facing::Right => (top + along, left + stride - 1),
+                        Facing::Down => (top + stride - 1, left + along),
+                        Facing::Left => (top + along, left),
+                        Facing::Up => (top, left + along),
+                    };
+                    map.is_tile(row, col)
+                })
+                .count()
+        };
+
+        let mut gluings = BTreeMap::new();
+        for (i, line) in lines.iter().filter(|(_, line)| !line.starts_with("size:")) {
+            let mut glue = |face, side, gluing: Gluing| -> eyre::Result<()> {
+                if !is_face(face) {
+                    return Err(eyre!("line {i}: there's no face at {face:?}"));
+                }
+                if !is_open(face, side) {
+                    return Err(eyre!(
+                        "line {i}: the {side:?} side of {face:?} touches another face"
+                    ));
+                }
+                let length = side_length(face, side);
+                if length != stride {
+                    return Err(eyre!(
+                        "line {i}: the {side:?} side of {face:?} is {length} tiles long, not {stride}"
+                    ));
+                }
+                if gluings.insert((face, side), gluing).is_some() {
+                    return Err(eyre!(
+                        "line {i}: the {side:?} side of {face:?} is glued twice"
+                    ));
+                }
+                Ok(())
+            };
+
+            let (from, to) = line
+                .split_once("->")
+                .ok_or(eyre!("line {i}: expected FACE SIDE -> FACE SIDE [flipped]"))?;
+            let from: Vec<&str> = from.split_whitespace().collect();
+            let to: Vec<&str> = to.split_whitespace().collect();
+            let flipped = match to.get(2) {
+                None => false,
+                Some(&"flipped") if to.len() == 3 => true,
+                Some(_) => return Err(eyre!("line {i}: unexpected {:?}", &to[2..])),
+            };
+            let (a, a_side, b, b_side) = match (from.as_slice(), to.as_slice()) {
+                ([a, a_side], [b, b_side, ..]) => (
+                    parse_face(a).ok_or(eyre!("line {i}: bad face {a:?}"))?,
+                    parse_side(a_side).ok_or(eyre!("line {i}: bad side {a_side:?}"))?,
+                    parse_face(b).ok_or(eyre!("line {i}: bad face {b:?}"))?,
+                    parse_side(b_side).ok_or(eyre!("line {i}: bad side {b_side:?}"))?,
+                ),
+                _ => return Err(eyre!("line {i}: expected FACE SIDE -> FACE SIDE [flipped]")),
+            };
+
+            let there = Gluing {
+                face: b,
+                side: b_side,
+                flipped,
+            };
+            let back = Gluing {
+                face: a,
+                side: a_side,
+                flipped,
+            };
+            glue(a, a_side, there)?;
+            if (a, a_side) != (b, b_side) {
+                glue(b, b_side, back)?;
+            }
+        }
+
+        let rows = map.spots.len().div_ceil(stride);
+        let cols = map
+            .spots
+            .first()
+            .map_or(0, |row| row.len())
+            .div_ceil(stride);
+        for face in (0..rows).flat_map(|row| (0..cols).map(move |col| (row, col))) {
+            for side in [Facing::Right, Facing::Down, Facing::Left, Facing::Up] {
+                if is_face(face) && is_open(face, side) && !gluings.contains_key(&(face, side)) {
+                    return Err(eyre!(
+                        "The {side:?} side of {face:?} isn't glued to anything"
+                    ));
+                }
+            }
+        }
+
+        Ok(GluedWrap { stride, gluings })
+    }
+}
+
+impl WrapRule for GluedWrap {
+    fn next(&self, character: &Character) -> Option<Character> {
+        let stride = self.stride;
+        let face = (character.row / stride, character.col / stride);
+        let (row, col) = (character.row % stride, character.col % stride);
+        let (along, leaving) = match character.facing {
+            Facing::Right => (row, col + 1 == stride),
+            Facing::Down => (col, row + 1 == stride),
+            Facing::Left => (row, col == 0),
+            Facing::Up => (col, row == 0),
+        };
+
+        match self.gluings.get(&(face, character.facing)) {
+            Some(gluing) if leaving => {
+                let along = if gluing.flipped {
+                    stride - 1 - along
+                } else {
+                    along
+                };
+                let (top, left) = (gluing.face.0 * stride, gluing.face.1 * stride);
+                let (row, col) = match gluing.side {
+                    Facing::Right => (top + along, left + stride - 1),
+                    Facing::Down => (top + stride - 1, left + along),
+                    Facing::Left => (top + along, left),
+                    Facing::Up => (top, left + along),
+                };
+                Some(Character {
+                    row,
+                    col,
+                    facing: gluing.side.turn_right().turn_right(),
+                    ..*character
+                })
+            }
+            _ => {
+                let (row, col) = match character.facing {
+                    Facing::Right => (character.row, character.col + 1),
+                    Facing::Down => (character.row + 1, character.col),
+                    Facing::Left => (character.row, character.col.checked_sub(1)?),
+                    Facing::Up => (character.row.checked_sub(1)?, character.col),
+                };
+                Some(Character {
+                    row,
+                    col,
+                    ..*character
+                })
+            }
+        }
+    }
+}
+
+/// One move forward along the path, across an edge or not.
+#[derive(Copy, Clone, Debug)]
+pub struct Step {
+    from: Character,
+    to: Character,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub struct Character {
+    row: usize,
+    col: usize,
+    facing: Facing,
+    face: Face,
+}
+
+impl Character {
+    fn instruct(
+        &mut self,
+        instruction: &Instruction,
+        rule: &dyn WrapRule,
+        map: &mut Map,
+        steps: &mut Vec<Step>,
+    ) {
+        match instruction {
+            Instruction::Advance(count) => {
+                for _ in 0..*count {
+                    let _ = self.advance(rule, map, steps);
+                }
+                trace!("{}", map);
+            }
+            Instruction::TurnLeft => self.facing = self.facing.turn_left(),
+            Instruction::TurnRight => self.facing = self.facing.turn_right(),
+        }
+    }
+
+    fn advance(&mut self, rule: &dyn WrapRule, map: &mut Map, steps: &mut Vec<Step>) -> Option<()> {
+        let next = rule.next(self)?;
+        if map.spots[next.row][next.col] != '#' {
+            map.spots[self.row][self.col] = self.facing.to_char();
+            steps.push(Step {
+                from: *self,
+                to: next,
+            });
+            *self = next;
+            map.spots[self.row][self.col] = self.facing.to_char();
+        }
+
+        Some(())
+    }
+
+    fn password(&self) -> usize {
+        1000 * (self.row + 1) + 4 * (self.col + 1) + self.facing.to_usize()
+    }
+}
+
+fn parse_map(input: &Input) -> Result<(Map, Vec<Instruction>), InputError> {
+    let mut paragraphs = input.paragraphs();
+    let grid = paragraphs
+        .next()
+        .ok_or_else(|| input.error(1, "the input is empty"))?;
+    let spots: Spots = grid.lines().map(|line| line.chars().collect()).collect();
+
+    let path_line = paragraphs
+        .next()
+        .and_then(|paragraph| paragraph.lines().next())
+        .ok_or_else(|| grid.error("the map isn't followed by a path"))?;
+    let instructions = path_line.parse_with(path)?;
+
+    Ok((Map::new(spots), instructions))
+}
+
+/// Follow the path using the given wrap rule, log the trail it leaves and
+/// return the final password along with every step taken.
+pub fn walk(instructions: &[Instruction], mut map: Map, rule: &dyn WrapRule) -> (usize, Vec<Step>) {
+    let mut character = Character {
+        col: map.spots[0].iter().position(|spot| *spot == '.').unwrap(),
+        row: 0,
+        face: Face::Top,
+        facing: Facing::Right,
+    };
+
+    let mut steps = Vec::new();
+    for instruction in instructions.iter() {
+        character.instruct(instruction, rule, &mut map, &mut steps);
+    }
+    map.spots[character.row][character.col] = character.facing.to_char();
+    debug!("{}", map);
+    (character.password(), steps)
+}
+
+/// Size of a map cell in the SVG, in pixels.
+const CELL: f64 = 6.0;
+
+fn face_tint(face: Face) -> &'static str {
+    match face {
+        Face::Top => "#f9e79f",
+        Face::Bottom => "#aed6f1",
+        Face::Left => "#abebc6",
+        Face::Right => "#f5cba7",
+        Face::Front => "#d7bde2",
+        Face::Back => "#f5b7b1",
+    }
+}
+
+fn facing_offset(facing: Facing) -> (f64, f64) {
+    match facing {
+        Facing::Right => (1.0, 0.0),
+        Facing::Down => (0.0, 1.0),
+        Facing::Left => (-1.0, 0.0),
+        Facing::Up => (0.0, -1.0),
+    }
+}
+
+fn svg_polyline(points: &[(f64, f64)], color: &str) -> String {
+    let points: Vec<String> = points
+        .iter()
+        .map(|(x, y)| format!("{x:.2},{y:.2}"))
+        .collect();
+    format!(
+        "  <polyline points=\"{}\" fill=\"none\" stroke=\"{color}\" stroke-width=\"{:.2}\" stroke-linejoin=\"round\"/>\n",
+        points.join(" "),
+        CELL / 3.0
+    )
+}
+
+fn svg_arrow(from: (f64, f64), to: (f64, f64)) -> String {
+    format!(
+        "  <line x1=\"{:.2}\" y1=\"{:.2}\" x2=\"{:.2}\" y2=\"{:.2}\" stroke=\"crimson\" stroke-width=\"{:.2}\" marker-end=\"url(#arrow)\"/>\n",
+        from.0,
+        from.1,
+        to.0,
+        to.1,
+        CELL / 4.0
+    )
+}
+
+/// Where a point on a face ends up on a cube of side `stride` centred on the
+/// origin, given in cells down and right from the face's top left corner.
+fn cube_point(cubeface: &CubeFace, down: f64, right: f64) -> [f64; 3] {
+    let half = cubeface.stride as f64 / 2.0;
+    let Orientation {
+        normal,
+        right: r,
+        down: d,
+    } = cubeface.orientation;
+    [0, 1, 2].map(|i| {
+        normal[i] as f64 * half + r[i] as f64 * (right - half) + d[i] as f64 * (down - half)
+    })
+}
+
+/// Isometric projection looking at the Top, Front and Right faces, or at the
+/// Bottom, Back and Left faces after turning the cube over.
+fn project(point: [f64; 3], turned_over: bool) -> (f64, f64) {
+    let [x, y, z] = if turned_over {
+        [point[1], point[0], -point[2]]
+    } else {
+        point
+    };
+    ((x + y) / 2f64.sqrt(), -(-x + y + 2.0 * z) / 6f64.sqrt())
+}
+
+fn is_visible(cubeface: &CubeFace, turned_over: bool) -> bool {
+    let [x, y, z] = cubeface.orientation.normal;
+    let (x, y, z) = if turned_over { (y, x, -z) } else { (x, y, z) };
+    x - y + z > 0
+}
+
+/// Draw one isometric view of the folded cube, with the path where it runs
+/// over the visible faces, centred on `origin`.
+fn svg_folded_view(
+    map: &Map,
+    cube: &CubeWrap,
+    steps: &[Step],
+    turned_over: bool,
+    origin: (f64, f64),
+) -> String {
+    let to_svg = |cubeface: &CubeFace, down: f64, right: f64| {
+        let (x, y) = project(cube_point(cubeface, down, right), turned_over);
+        (origin.0 + x * CELL, origin.1 + y * CELL)
+    };
+    let polygon = |corners: [(f64, f64); 4], fill: &str| {
+        let points: Vec<String> = corners
+            .iter()
+            .map(|(x, y)| format!("{x:.2},{y:.2}"))
+            .collect();
+        format!(
+            "  <polygon points=\"{}\" fill=\"{fill}\"/>\n",
+            points.join(" ")
+        )
+    };
+    let square = |cubeface: &CubeFace, down: f64, right: f64, size: f64| {
+        [
+            to_svg(cubeface, down, right),
+            to_svg(cubeface, down, right + size),
+            to_svg(cubeface, down + size, right + size),
+            to_svg(cubeface, down + size, right),
+        ]
+    };
+
+    let mut svg = String::new();
+    let visible: Vec<&CubeFace> = cube
+        .faces
+        .iter()
+        .filter(|cubeface| is_visible(cubeface, turned_over))
+        .collect();
+    for cubeface in visible.iter() {
+        let stride = cubeface.stride as f64;
+        svg += &polygon(square(cubeface, 0.0, 0.0, stride), face_tint(cubeface.face));
+        for row in 0..cubeface.stride {
+            for col in 0..cubeface.stride {
+                if map.spots[cubeface.row + row][cubeface.col + col] == '#' {
+                    svg += &polygon(square(cubeface, row as f64, col as f64, 1.0), "#333");
+                }
+            }
+        }
+    }
+
+    let centre = |character: &Character| {
+        let cubeface = visible
+            .iter()
+            .find(|cubeface| cubeface.face == character.face)?;
+        Some(to_svg(
+            cubeface,
+            (character.row - cubeface.row) as f64 + 0.5,
+            (character.col - cubeface.col) as f64 + 0.5,
+        ))
+    };
+    let mut points = Vec::new();
+    for step in steps {
+        match (centre(&step.from), centre(&step.to)) {
+            (Some(from), Some(to)) => {
+                if points.is_empty() {
+                    points.push(from);
+                }
+                points.push(to);
+            }
+            _ => {
+                if points.len() > 1 {
+                    svg += &svg_polyline(&points, "black");
+                }
+                points.clear();
+            }
+        }
+    }
+    if points.len() > 1 {
+        svg += &svg_polyline(&points, "black");
+    }
+
+    svg
+}
+
+/// Draw the map as an SVG: every face of the cube in its own tint, the walls,
+/// the path walked over the top, and arrows wherever the path crosses from
+/// one face to another. Crossings where the path jumps across the net are
+/// numbered at both ends. With `folded`, two isometric views of the folded
+/// cube are drawn next to the net.
+pub fn write_svg(map: &Map, cube: &CubeWrap, steps: &[Step], folded: bool) -> String {
+    let h = map.spots.len() as f64 * CELL;
+    let w = map.spots[0].len() as f64 * CELL;
+    let panel = 3.0 * cube.stride as f64 * CELL;
+    let (width, height) = if folded {
+        (w + panel, h.max(2.0 * panel))
+    } else {
+        (w, h)
+    };
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width:.0}\" height=\"{height:.0}\" viewBox=\"0 0 {width:.0} {height:.0}\">\n"
+    );
+    svg += "  <defs>\n";
+    svg += "    <marker id=\"arrow\" viewBox=\"0 0 10 10\" refX=\"5\" refY=\"5\" markerWidth=\"4\" markerHeight=\"4\" orient=\"auto\">\n";
+    svg += "      <path d=\"M 0 0 L 10 5 L 0 10 z\" fill=\"crimson\"/>\n";
+    svg += "    </marker>\n";
+    svg += "  </defs>\n";
+    svg += &format!("  <rect width=\"{width:.0}\" height=\"{height:.0}\" fill=\"white\"/>\n");
+
+    for cubeface in cube.faces.iter() {
+        svg += &format!(
+            "  <rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\" fill=\"{}\"/>\n",
+            cubeface.col as f64 * CELL,
+            cubeface.row as f64 * CELL,
+            cube.stride as f64 * CELL,
+            cube.stride as f64 * CELL,
+            face_tint(cubeface.face)
+        );
+    }
+
+    for (row, spots) in map.spots.iter().enumerate() {
+        for (col, _) in spots.iter().enumerate().filter(|(_, &spot)| spot == '#') {
+            svg += &format!(
+                "  <rect x=\"{:.2}\" y=\"{:.2}\" width=\"{CELL:.2}\" height=\"{CELL:.2}\" fill=\"#333\"/>\n",
+                col as f64 * CELL,
+                row as f64 * CELL
+            );
+        }
+    }
+
+    let centre = |character: &Character| {
+        (
+            (character.col as f64 + 0.5) * CELL,
+            (character.row as f64 + 0.5) * CELL,
+        )
+    };
+    let mut points = Vec::new();
+    let mut arrows = String::new();
+    let mut jumps = 0;
+    for step in steps {
+        let (from, to) = (centre(&step.from), centre(&step.to));
+        let adjacent =
+            step.from.row.abs_diff(step.to.row) + step.from.col.abs_diff(step.to.col) == 1;
+        if adjacent {
+            if points.is_empty() {
+                points.push(from);
+            }
+            points.push(to);
+            if step.from.face != step.to.face {
+                arrows += &svg_arrow(from, to);
+            }
+            continue;
+        }
+
+        if points.len() > 1 {
+            svg += &svg_polyline(&points, "black");
+        }
+        points.clear();
+
+        jumps += 1;
+        let (dx, dy) = facing_offset(step.from.facing);
+        arrows += &svg_arrow(from, (from.0 + dx * 2.0 * CELL, from.1 + dy * 2.0 * CELL));
+        let (dx, dy) = facing_offset(step.to.facing);
+        let outside = (to.0 - dx * 2.0 * CELL, to.1 - dy * 2.0 * CELL);
+        arrows += &svg_arrow(outside, to);
+        for (x, y) in [from, outside] {
+            arrows += &format!(
+                "  <text x=\"{x:.2}\" y=\"{y:.2}\" font-size=\"{:.2}\" fill=\"crimson\">{jumps}</text>\n",
+                2.0 * CELL
+            );
+        }
+    }
+    if points.len() > 1 {
+        svg += &svg_polyline(&points, "black");
+    }
+    svg += &arrows;
+
+    if folded {
+        let x = w + panel / 2.0;
+        svg += &svg_folded_view(map, cube, steps, false, (x, panel / 2.0));
+        svg += &svg_folded_view(map, cube, steps, true, (x, 1.5 * panel));
+    }
+
+    svg += "</svg>\n";
+    svg
+}
+
+/// The map and the path written under it.
+pub fn parse(input: &Input) -> Result<(Map, Vec<Instruction>), InputError> {
+    let (map, instructions) = parse_map(input)?;
+    debug!("{}", map);
+    Ok((map, instructions))
+}
+
+pub fn part_one((map, instructions): &(Map, Vec<Instruction>)) -> usize {
+    walk(instructions, map.clone(), &FlatWrap::new(map)).0
+}
+
+pub fn part_two((map, instructions): &(Map, Vec<Instruction>)) -> eyre::Result<usize> {
+    Ok(walk(instructions, map.clone(), &CubeWrap::new(map)?).0)
+}
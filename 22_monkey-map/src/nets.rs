@@ -0,0 +1,198 @@
+//! Every cube net folded up in every size, and checks of how the folded
+//! cubes glue their faces together, for `tests/nets.rs`.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use eyre::eyre;
+
+use crate::{Character, CubeWrap, Edge, Facing, Map, WrapRule};
+
+impl CubeWrap {
+    /// The length of a side of each face.
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+}
+
+/// The 11 cube nets, one face per character.
+const NETS: [&str; 11] = [
+    "#...\n####\n#...",
+    "#...\n####\n.#..",
+    "#...\n####\n..#.",
+    "#...\n####\n...#",
+    ".#..\n####\n.#..",
+    ".#..\n####\n..#.",
+    "##..\n.###\n.#..",
+    "##..\n.###\n..#.",
+    "##..\n.###\n...#",
+    "##..\n.##.\n..##",
+    "###..\n..###",
+];
+
+/// Every rotation and reflection of every net, as grids of faces.
+pub fn net_layouts() -> BTreeSet<Vec<Vec<bool>>> {
+    let mut layouts = BTreeSet::new();
+    for net in NETS {
+        let mut layout: Vec<Vec<bool>> = net
+            .lines()
+            .map(|line| line.chars().map(|c| c == '#').collect())
+            .collect();
+        for _ in 0..4 {
+            let mirrored = layout
+                .iter()
+                .map(|row| row.iter().rev().copied().collect())
+                .collect();
+            layouts.insert(mirrored);
+            layout = (0..layout[0].len())
+                .map(|col| layout.iter().rev().map(|row| row[col]).collect())
+                .collect();
+            layouts.insert(layout.clone());
+        }
+    }
+    layouts
+}
+
+/// Blow a layout of faces up into an open map with faces of the given size.
+pub fn net_map(layout: &[Vec<bool>], stride: usize) -> Map {
+    Map::new(
+        layout
+            .iter()
+            .flat_map(|row| {
+                let spots: Vec<char> = row
+                    .iter()
+                    .flat_map(|&face| std::iter::repeat_n(if face { '.' } else { ' ' }, stride))
+                    .collect();
+                std::iter::repeat_n(spots, stride)
+            })
+            .collect(),
+    )
+}
+
+/// Check that the faces and edges of a folded net come out as a cube, glued
+/// the way the net is drawn.
+pub fn check_folding(cube: &CubeWrap) -> eyre::Result<()> {
+    let stride = cube.stride;
+    let mut edge_counts: BTreeMap<Edge, usize> = BTreeMap::new();
+    for cubeface in cube.faces.iter() {
+        for edge in cubeface.edges {
+            *edge_counts.entry(edge).or_default() += 1;
+        }
+    }
+    if edge_counts.len() != 12 || edge_counts.values().any(|&count| count != 2) {
+        return Err(eyre!("Edges aren't shared by two faces: {edge_counts:?}"));
+    }
+
+    for a in cube.faces.iter() {
+        for b in cube.faces.iter() {
+            let glued = if a.row == b.row && a.col + stride == b.col {
+                Some((a.right(), b.left()))
+            } else if a.col == b.col && a.row + stride == b.row {
+                Some((a.bottom(), b.top()))
+            } else {
+                None
+            };
+            if let Some((edge_a, edge_b)) = glued {
+                if edge_a != edge_b {
+                    return Err(eyre!(
+                        "{a} and {b} touch in the net but meet at {edge_a:?} and {edge_b:?}"
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// The character standing on every cell of the cube facing every way.
+fn every_start(cube: &CubeWrap) -> impl Iterator<Item = Character> + '_ {
+    cube.faces.iter().flat_map(move |cubeface| {
+        (cubeface.row..cubeface.row + cube.stride).flat_map(move |row| {
+            (cubeface.col..cubeface.col + cube.stride).flat_map(move |col| {
+                [Facing::Right, Facing::Down, Facing::Left, Facing::Up].map(|facing| Character {
+                    row,
+                    col,
+                    facing,
+                    face: cubeface.face,
+                })
+            })
+        })
+    })
+}
+
+/// Walk `steps` steps straight ahead on the cube, ignoring walls.
+fn walk_straight(
+    cube: &CubeWrap,
+    mut character: Character,
+    steps: usize,
+) -> eyre::Result<Character> {
+    for _ in 0..steps {
+        character = cube
+            .next(&character)
+            .ok_or_else(|| eyre!("{character:?} walked off the cube"))?;
+    }
+    Ok(character)
+}
+
+fn turn_around(character: Character) -> Character {
+    Character {
+        facing: character.facing.turn_right().turn_right(),
+        ..character
+    }
+}
+
+/// Check that going `4 * stride` steps straight from every cell in every
+/// direction comes back to the start.
+pub fn check_walks_around(cube: &CubeWrap) -> eyre::Result<()> {
+    for start in every_start(cube) {
+        let around = walk_straight(cube, start, 4 * cube.stride)?;
+        if around != start {
+            return Err(eyre!(
+                "Walking {} steps from {start:?} ended at {around:?}",
+                4 * cube.stride
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Check that walking forward from every cell in every direction, turning
+/// around and walking back again ends up where it started.
+pub fn check_walks_back(cube: &CubeWrap) -> eyre::Result<()> {
+    let stride = cube.stride;
+    for start in every_start(cube) {
+        for steps in [1, stride + 1, 3 * stride - 1] {
+            let there = walk_straight(cube, start, steps)?;
+            let back = turn_around(walk_straight(cube, turn_around(there), steps)?);
+            if back != start {
+                return Err(eyre!(
+                    "Walking {steps} steps from {start:?} and back ended at {back:?}"
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Check that every cell on the edge of a face crosses onto another face,
+/// and no two edge crossings land on the same cell facing the same way.
+pub fn check_crossings(cube: &CubeWrap) -> eyre::Result<()> {
+    let mut crossings = BTreeMap::new();
+    for start in every_start(cube) {
+        let next = walk_straight(cube, start, 1)?;
+        if next.face != start.face {
+            if let Some(other) = crossings.insert(next, start) {
+                return Err(eyre!("{other:?} and {start:?} both cross onto {next:?}"));
+            }
+        }
+    }
+
+    if crossings.len() != 24 * cube.stride {
+        return Err(eyre!(
+            "Found {} edge crossings, expected {}",
+            crossings.len(),
+            24 * cube.stride
+        ));
+    }
+    Ok(())
+}
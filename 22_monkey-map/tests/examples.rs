@@ -0,0 +1,3 @@
+use monkey_map::{parse, part_one, part_two};
+
+aoc::examples!(parse, part_one, part_two);
@@ -0,0 +1,68 @@
+use monkey_map::{
+    nets::{
+        check_crossings, check_folding, check_walks_around, check_walks_back, net_layouts, net_map,
+    },
+    CubeWrap,
+};
+
+/// The face sizes every net is folded in.
+const STRIDES: [usize; 6] = [1, 2, 3, 4, 7, 12];
+
+/// Every orientation of every net folded up in every size, along with the
+/// layout drawn out for assertion messages.
+fn cubes() -> impl Iterator<Item = (String, CubeWrap)> {
+    net_layouts().into_iter().flat_map(|layout| {
+        let drawn: String = layout
+            .iter()
+            .map(|row| {
+                let row: String = row
+                    .iter()
+                    .map(|&face| if face { '#' } else { '.' })
+                    .collect();
+                row + "\n"
+            })
+            .collect();
+        STRIDES.into_iter().map(move |stride| {
+            let cube = CubeWrap::new(&net_map(&layout, stride))
+                .unwrap_or_else(|e| panic!("net of size {stride} doesn't fold:\n{drawn}{e}"));
+            assert_eq!(cube.stride(), stride, "face size of\n{drawn}");
+            (format!("net of size {stride}:\n{drawn}"), cube)
+        })
+    })
+}
+
+#[test]
+fn every_net_folds_into_a_cube() {
+    for (net, cube) in cubes() {
+        if let Err(e) = check_folding(&cube) {
+            panic!("{net}{e}");
+        }
+    }
+}
+
+#[test]
+fn walking_around_every_net_comes_back() {
+    for (net, cube) in cubes() {
+        if let Err(e) = check_walks_around(&cube) {
+            panic!("{net}{e}");
+        }
+    }
+}
+
+#[test]
+fn walking_there_and_back_on_every_net_is_the_identity() {
+    for (net, cube) in cubes() {
+        if let Err(e) = check_walks_back(&cube) {
+            panic!("{net}{e}");
+        }
+    }
+}
+
+#[test]
+fn every_net_crosses_each_edge_once() {
+    for (net, cube) in cubes() {
+        if let Err(e) = check_crossings(&cube) {
+            panic!("{net}{e}");
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use aoc::Input;
+use monkey_map::{parse, walk, write_svg, CubeWrap};
+
+const EXAMPLE: &str = include_str!("../examples/example.txt");
+
+/// Walk the example map along the given path on the cube and render it,
+/// returning the SVG and the number of moves taken.
+fn render(path: &str, folded: bool) -> (String, usize) {
+    let raw = EXAMPLE.split("\n\n").next().unwrap().to_owned() + "\n\n" + path + "\n";
+    let (map, instructions) = parse(&Input::new("example", &raw)).unwrap();
+    let cube = CubeWrap::new(&map).unwrap();
+    let (_, steps) = walk(&instructions, map.clone(), &cube);
+    (write_svg(&map, &cube, &steps, folded), steps.len())
+}
+
+/// Check that every tag is closed in the order it was opened, and return the
+/// tags in the order they were opened.
+fn tags(svg: &str) -> Vec<&str> {
+    let mut open = Vec::new();
+    let mut tags = Vec::new();
+    for tag in svg.split('<').skip(1) {
+        let tag = &tag[..tag.find('>').expect("unterminated tag")];
+        if let Some(name) = tag.strip_prefix('/') {
+            assert_eq!(open.pop(), Some(name), "mismatched </{name}>");
+        } else {
+            let name = tag.split_whitespace().next().unwrap();
+            tags.push(tag);
+            if !tag.ends_with('/') {
+                open.push(name);
+            }
+        }
+    }
+    assert!(open.is_empty(), "unclosed {open:?}");
+    tags
+}
+
+/// The points of each polyline.
+fn polylines(svg: &str) -> Vec<usize> {
+    tags(svg)
+        .into_iter()
+        .filter(|tag| tag.starts_with("polyline"))
+        .map(|tag| {
+            let points = tag.split("points=\"").nth(1).unwrap();
+            points[..points.find('"').unwrap()]
+                .split_whitespace()
+                .count()
+        })
+        .collect()
+}
+
+#[test]
+fn svg_is_well_formed() {
+    for folded in [false, true] {
+        let (svg, _) = render(EXAMPLE.lines().last().unwrap(), folded);
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.ends_with("</svg>\n"));
+        tags(&svg);
+    }
+}
+
+#[test]
+fn svg_has_a_rect_per_face() {
+    let (svg, _) = render("2R3", false);
+    let faces = tags(&svg)
+        .into_iter()
+        .filter(|tag| tag.starts_with("rect ") && tag.contains("width=\"24.00\""))
+        .count();
+    assert_eq!(faces, 6);
+}
+
+#[test]
+fn svg_path_has_a_point_per_move() {
+    // Two tiles right and three down, staying on the first face.
+    let (svg, moves) = render("2R3", false);
+    assert_eq!(moves, 5);
+    assert_eq!(polylines(&svg), [moves + 1]);
+}
@@ -0,0 +1,71 @@
+use aoc::Input;
+use monkey_map::{parse, walk, GluedWrap};
+
+const EXAMPLE: &str = include_str!("../examples/example.txt");
+
+/// One open 3x3 face, turning to face up and taking a step off the top.
+const SQUARE: &str = "...\n...\n...\n\nL1\n";
+
+fn password(raw: &str, wrap: &str) -> usize {
+    let (map, instructions) = parse(&Input::new("test", raw)).unwrap();
+    let rule = GluedWrap::parse(&map, wrap).unwrap();
+    walk(&instructions, map, &rule).0
+}
+
+fn parse_error(raw: &str, wrap: &str) -> String {
+    let (map, _) = parse(&Input::new("test", raw)).unwrap();
+    match GluedWrap::parse(&map, wrap) {
+        Ok(_) => panic!("parsed {wrap:?}"),
+        Err(e) => e.to_string(),
+    }
+}
+
+#[test]
+fn torus_wrap_file() {
+    let wrap = include_str!("../wraps/torus-example.txt");
+    assert_eq!(password(EXAMPLE, wrap), 6032);
+}
+
+#[test]
+fn klein_wrap_file() {
+    let wrap = include_str!("../wraps/klein-example.txt");
+    assert_eq!(password(EXAMPLE, wrap), 6032);
+}
+
+#[test]
+fn flipped_gluing_comes_in_mirrored() {
+    let torus = "size: 3\n0,0 left -> 0,0 right\n0,0 up -> 0,0 down\n";
+    let klein = "size: 3\n0,0 left -> 0,0 right\n0,0 up -> 0,0 down flipped\n";
+    assert_eq!(password(SQUARE, torus), 3007);
+    assert_eq!(password(SQUARE, klein), 3015);
+}
+
+#[test]
+fn edge_glued_twice() {
+    let wrap = format!(
+        "{}0,2 left -> 1,0 up\n",
+        include_str!("../wraps/torus-example.txt")
+    );
+    assert_eq!(
+        parse_error(EXAMPLE, &wrap),
+        "line 10: the Left side of (0, 2) is glued twice"
+    );
+}
+
+#[test]
+fn mismatched_edge_lengths() {
+    let map = "....\n..\n\n1\n";
+    let wrap = "size: 2\n0,0 left -> 0,1 right\n";
+    assert_eq!(
+        parse_error(map, wrap),
+        "line 2: the Right side of (0, 1) is 1 tiles long, not 2"
+    );
+}
+
+#[test]
+fn unknown_face() {
+    assert_eq!(
+        parse_error(EXAMPLE, "size: 4\n5,5 left -> 0,2 right\n"),
+        "line 2: there's no face at (5, 5)"
+    );
+}
@@ -0,0 +1,643 @@
+//! Day 23: Unstable Diffusion.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use aoc::Input;
+use eyre::eyre;
+use log::{debug, log_enabled, trace, Level};
+
+/// A position or offset, with as many coordinates as the ruleset uses. The
+/// first two are x and y from the input, north being +y.
+pub type Point = Vec<i64>;
+
+pub fn parse_positions(s: &str, dimensions: usize) -> Option<BTreeSet<Point>> {
+    let mut positions = BTreeSet::new();
+
+    for (y, line) in s.lines().enumerate() {
+        line.chars()
+            .enumerate()
+            .filter(|(_, c)| *c == '#')
+            .for_each(|(x, _)| {
+                let y = -(y as i64);
+                trace!("Found elf at {x}, {y}");
+                let mut position = vec![0; std::cmp::max(dimensions, 2)];
+                position[0] = x as i64;
+                position[1] = y;
+                positions.insert(position);
+            });
+    }
+
+    Some(normalize(positions))
+}
+
+fn offset(position: &Point, step: &Point) -> Point {
+    position
+        .iter()
+        .zip(step.iter())
+        .map(|(p, s)| p + s)
+        .collect()
+}
+
+/// An elf may move `step` away if nobody is at any of `must_be_empty`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Rule {
+    step: Point,
+    must_be_empty: Vec<Point>,
+}
+
+/// An elf with nobody at any of `neighbours` stays put, otherwise it takes the
+/// first rule that applies. Each round starts looking one rule further along.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Ruleset {
+    neighbours: Vec<Point>,
+    rules: Vec<Rule>,
+}
+
+const DAY23_RULES: &str = "\
+# Offsets are x,y with north being +y.
+rule: 0,1 needs -1,1 0,1 1,1
+rule: 0,-1 needs -1,-1 0,-1 1,-1
+rule: -1,0 needs -1,1 -1,0 -1,-1
+rule: 1,0 needs 1,1 1,0 1,-1
+";
+
+impl Ruleset {
+    pub fn day23() -> Ruleset {
+        Ruleset::parse(DAY23_RULES).unwrap()
+    }
+
+    /// Reads one `rule: STEP needs OFFSET OFFSET ...` per line, tried in the
+    /// order given. An optional `neighbours: OFFSET OFFSET ...` line says what
+    /// an elf checks before moving at all, defaulting to every cell any rule
+    /// needs empty. Offsets are comma separated and all of one length, so hex
+    /// grids (axial x,y) and 3D grids (x,y,z) work too. `#` starts a comment.
+    pub fn parse(s: &str) -> eyre::Result<Ruleset> {
+        let parse_point = |line: usize, point: &str| -> eyre::Result<Point> {
+            point
+                .split(',')
+                .map(|n| n.trim().parse::<i64>())
+                .collect::<Result<Point, _>>()
+                .map_err(|_| eyre!("line {}: can't read offset {:?}", line, point))
+        };
+
+        let mut neighbours = None;
+        let mut rules = Vec::new();
+        for (i, line) in s.lines().enumerate() {
+            let line_number = i + 1;
+            let line = line.split('#').next().unwrap().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(points) = line.strip_prefix("neighbours:") {
+                neighbours = Some(
+                    points
+                        .split_whitespace()
+                        .map(|point| parse_point(line_number, point))
+                        .collect::<eyre::Result<Vec<Point>>>()?,
+                );
+            } else if let Some(rule) = line.strip_prefix("rule:") {
+                let (step, must_be_empty) = rule.split_once("needs").ok_or(eyre!(
+                    "line {}: expected \"rule: STEP needs ...\"",
+                    line_number
+                ))?;
+                rules.push(Rule {
+                    step: parse_point(line_number, step.trim())?,
+                    must_be_empty: must_be_empty
+                        .split_whitespace()
+                        .map(|point| parse_point(line_number, point))
+                        .collect::<eyre::Result<Vec<Point>>>()?,
+                });
+            } else {
+                return Err(eyre!("line {}: unexpected {:?}", line_number, line));
+            }
+        }
+
+        if rules.is_empty() {
+            return Err(eyre!("no rules given"));
+        }
+        let neighbours = neighbours.unwrap_or_else(|| {
+            let all: BTreeSet<Point> = rules
+                .iter()
+                .flat_map(|rule| rule.must_be_empty.iter().cloned())
+                .collect();
+            all.into_iter().collect()
+        });
+
+        let ruleset = Ruleset { neighbours, rules };
+        let dimensions = ruleset.dimensions();
+        let points = ruleset.neighbours.iter().chain(
+            ruleset
+                .rules
+                .iter()
+                .flat_map(|rule| std::iter::once(&rule.step).chain(rule.must_be_empty.iter())),
+        );
+        for point in points {
+            if point.len() != dimensions {
+                return Err(eyre!(
+                    "offset {:?} doesn't have {} coordinates",
+                    point,
+                    dimensions
+                ));
+            }
+        }
+        Ok(ruleset)
+    }
+
+    pub fn dimensions(&self) -> usize {
+        self.rules[0].step.len()
+    }
+}
+
+fn add_proposal(
+    proposals: &mut BTreeMap<Point, Vec<Point>>,
+    proposal: Point,
+    elf_position: &Point,
+) {
+    if let Some(current_positions) = proposals.get_mut(&proposal) {
+        current_positions.push(elf_position.clone());
+    } else {
+        proposals.insert(proposal, vec![elf_position.clone()]);
+    }
+}
+
+fn generate_proposals(
+    positions: &BTreeSet<Point>,
+    ruleset: &Ruleset,
+    iteration: usize,
+) -> BTreeMap<Point, Vec<Point>> {
+    let mut proposals: BTreeMap<Point, Vec<Point>> = BTreeMap::new();
+    let is_empty = |elf_position: &Point, cells: &[Point]| {
+        cells
+            .iter()
+            .all(|cell| !positions.contains(&offset(elf_position, cell)))
+    };
+
+    for elf_position in positions.iter() {
+        let proposal = if is_empty(elf_position, &ruleset.neighbours) {
+            None
+        } else {
+            (0..ruleset.rules.len())
+                .map(|i| &ruleset.rules[(i + iteration) % ruleset.rules.len()])
+                .find(|rule| is_empty(elf_position, &rule.must_be_empty))
+                .map(|rule| offset(elf_position, &rule.step))
+        };
+        add_proposal(
+            &mut proposals,
+            proposal.unwrap_or_else(|| elf_position.clone()),
+            elf_position,
+        );
+    }
+
+    proposals
+}
+
+fn execute_proposals(proposals: &BTreeMap<Point, Vec<Point>>) -> BTreeSet<Point> {
+    let mut positions = BTreeSet::new();
+
+    proposals.iter().for_each(|(proposal, proposers)| {
+        if proposers.len() == 1 {
+            positions.insert(proposal.clone());
+        } else {
+            for proposer in proposers.iter() {
+                positions.insert(proposer.clone());
+            }
+        }
+    });
+
+    normalize(positions)
+}
+
+/// The smallest corner of the bounding box and the corner one past the largest.
+fn get_min_max(positions: &BTreeSet<Point>) -> (Point, Point) {
+    let dimensions = positions.iter().next().map_or(2, |position| position.len());
+    let mut minmax = positions.iter().fold(
+        (vec![i64::MAX; dimensions], vec![i64::MIN; dimensions]),
+        |(mut min, mut max), position| {
+            for (d, coordinate) in position.iter().enumerate() {
+                min[d] = std::cmp::min(min[d], *coordinate);
+                max[d] = std::cmp::max(max[d], *coordinate);
+            }
+            (min, max)
+        },
+    );
+    minmax.1.iter_mut().for_each(|max| *max += 1);
+    minmax
+}
+
+/// Logs the x,y plane, once per layer when there are more dimensions.
+fn log_map(positions: &BTreeSet<Point>) {
+    let (min, max) = get_min_max(positions);
+    let mut drawing = String::new();
+    let mut layers: BTreeMap<&[i64], Vec<&Point>> = BTreeMap::new();
+    for position in positions.iter() {
+        layers.entry(&position[2..]).or_default().push(position);
+    }
+    for (layer, _) in layers.iter() {
+        if !layer.is_empty() {
+            drawing += &format!("-- layer {:?} --\n", layer);
+        }
+        for y in (min[1]..max[1]).rev() {
+            for x in min[0]..max[0] {
+                let mut position = vec![x, y];
+                position.extend_from_slice(layer);
+                if positions.contains(&position) {
+                    drawing.push('#');
+                } else {
+                    drawing.push('.');
+                }
+            }
+            drawing.push('\n');
+        }
+    }
+    debug!("{drawing}");
+}
+
+/// A way of running the rounds. Positions use the coordinates from
+/// `parse_positions`, normalized so the smallest corner is at the origin.
+trait Engine {
+    /// Plays round `iteration`, counting from 0, and returns how many elves moved.
+    fn round(&mut self, iteration: usize) -> usize;
+    fn positions(&self) -> BTreeSet<Point>;
+
+    /// The smallest corner of the box around every elf and the corner one past
+    /// the largest, in the same coordinates as `positions`.
+    fn bounding_box(&self) -> (Point, Point) {
+        get_min_max(&self.positions())
+    }
+}
+
+/// The original engine, one set lookup per neighbour per elf, which can run
+/// any ruleset.
+struct SetEngine {
+    positions: BTreeSet<Point>,
+    ruleset: Ruleset,
+}
+
+impl Engine for SetEngine {
+    fn round(&mut self, iteration: usize) -> usize {
+        let proposals = generate_proposals(&self.positions, &self.ruleset, iteration);
+        let moved = proposals
+            .iter()
+            .filter(|(proposal, proposers)| proposers.len() == 1 && proposers[0] != **proposal)
+            .count();
+        self.positions = execute_proposals(&proposals);
+        moved
+    }
+
+    fn positions(&self) -> BTreeSet<Point> {
+        normalize(self.positions.clone())
+    }
+}
+
+/// Shifts a row one column east, so bit `x` of the result is bit `x - 1` of `row`.
+fn shift_east(row: &[u64]) -> Vec<u64> {
+    (0..row.len())
+        .map(|i| (row[i] << 1) | if i > 0 { row[i - 1] >> 63 } else { 0 })
+        .collect()
+}
+
+/// Shifts a row one column west, so bit `x` of the result is bit `x + 1` of `row`.
+fn shift_west(row: &[u64]) -> Vec<u64> {
+    (0..row.len())
+        .map(|i| {
+            (row[i] >> 1)
+                | if i + 1 < row.len() {
+                    row[i + 1] << 63
+                } else {
+                    0
+                }
+        })
+        .collect()
+}
+
+/// Elves stored as one bitset per row for the day 23 rules only, row 0 being
+/// the northernmost. Bit `x` of a row is column `x`, and `origin` is where
+/// row 0, column 0 sits in `parse_positions` coordinates. The field always
+/// keeps an empty row and column on every side, so neighbours never fall off
+/// the edge.
+struct BitEngine {
+    rows: Vec<Vec<u64>>,
+    origin: (i64, i64),
+}
+
+impl BitEngine {
+    fn new(positions: &BTreeSet<Point>) -> BitEngine {
+        let (min, max) = get_min_max(positions);
+        let (minx, miny, maxx, maxy) = (min[0], min[1], max[0], max[1]);
+        let words = ((maxx - minx) as usize + 2) / 64 + 1;
+        let mut engine = BitEngine {
+            rows: vec![vec![0; words]; (maxy - miny) as usize + 2],
+            origin: (minx - 1, maxy),
+        };
+        for position in positions.iter() {
+            let row = (engine.origin.1 - position[1]) as usize;
+            let col = (position[0] - engine.origin.0) as usize;
+            engine.rows[row][col / 64] |= 1 << (col % 64);
+        }
+        engine.grow();
+        engine
+    }
+
+    /// Adds empty rows or whole words of columns wherever elves reach the edge.
+    fn grow(&mut self) {
+        let words = self.rows[0].len();
+        if self.rows[0].iter().any(|word| *word != 0) {
+            self.rows.insert(0, vec![0; words]);
+            self.origin.1 += 1;
+        }
+        if self.rows[self.rows.len() - 1].iter().any(|word| *word != 0) {
+            self.rows.push(vec![0; words]);
+        }
+        if self.rows.iter().any(|row| row[0] & 1 != 0) {
+            self.rows.iter_mut().for_each(|row| row.insert(0, 0));
+            self.origin.0 -= 64;
+        }
+        if self.rows.iter().any(|row| row[words - 1] >> 63 != 0) {
+            self.rows.iter_mut().for_each(|row| row.push(0));
+        }
+    }
+}
+
+impl BitEngine {
+    /// Where each elf in row `y` proposes to go, as bitsets in the order
+    /// north, south, west, east.
+    fn row_proposals(&self, y: usize, iteration: usize) -> [Vec<u64>; 4] {
+        let words = self.rows[y].len();
+        let (n, c, s) = (&self.rows[y - 1], &self.rows[y], &self.rows[y + 1]);
+        let (n_east, n_west) = (shift_east(n), shift_west(n));
+        let (s_east, s_west) = (shift_east(s), shift_west(s));
+        let column: Vec<u64> = (0..words).map(|i| n[i] | c[i] | s[i]).collect();
+        let (column_east, column_west) = (shift_east(&column), shift_west(&column));
+
+        let mut proposals = [(); 4].map(|_| vec![0; words]);
+        for i in 0..words {
+            let free = [
+                !(n[i] | n_east[i] | n_west[i]),
+                !(s[i] | s_east[i] | s_west[i]),
+                !column_east[i],
+                !column_west[i],
+            ];
+            let alone = free[0] & free[1] & free[2] & free[3];
+            let mut undecided = c[i] & !alone;
+            for direction in (0..4).map(|d| (d + iteration) % 4) {
+                let proposing = undecided & free[direction];
+                proposals[direction][i] = proposing;
+                undecided &= !proposing;
+            }
+        }
+        proposals
+    }
+}
+
+impl Engine for BitEngine {
+    fn round(&mut self, iteration: usize) -> usize {
+        let height = self.rows.len();
+        let words = self.rows[0].len();
+        let empty = vec![0; words];
+
+        let mut proposals: Vec<[Vec<u64>; 4]> = vec![[(); 4].map(|_| empty.clone())];
+        proposals.extend((1..height - 1).map(|y| self.row_proposals(y, iteration)));
+        proposals.push([(); 4].map(|_| empty.clone()));
+
+        // Two elves can only want the same spot when they come at it from
+        // opposite sides, so every collision is between a north and a south
+        // mover two rows apart or a west and an east mover two columns apart.
+        let [north, south, west, east] = [0, 1, 2, 3].map(|d| {
+            proposals
+                .iter()
+                .map(|row| &row[d])
+                .collect::<Vec<&Vec<u64>>>()
+        });
+        let mut rows = self.rows.clone();
+        let mut moved = 0;
+        for y in 1..height - 1 {
+            let south_two_up = if y >= 2 { south[y - 2] } else { &empty };
+            let north_two_down = if y + 2 < height { north[y + 2] } else { &empty };
+            let east_two_left = shift_east(&shift_east(east[y]));
+            let west_two_right = shift_west(&shift_west(west[y]));
+
+            let go_north: Vec<u64> = (0..words).map(|i| north[y][i] & !south_two_up[i]).collect();
+            let go_south: Vec<u64> = (0..words)
+                .map(|i| south[y][i] & !north_two_down[i])
+                .collect();
+            let go_west: Vec<u64> = (0..words).map(|i| west[y][i] & !east_two_left[i]).collect();
+            let go_east: Vec<u64> = (0..words)
+                .map(|i| east[y][i] & !west_two_right[i])
+                .collect();
+            let (landing_west, landing_east) = (shift_west(&go_west), shift_east(&go_east));
+
+            for i in 0..words {
+                let leaving = go_north[i] | go_south[i] | go_west[i] | go_east[i];
+                moved += leaving.count_ones() as usize;
+                rows[y][i] &= !leaving;
+                rows[y][i] |= landing_west[i] | landing_east[i];
+                rows[y - 1][i] |= go_north[i];
+                rows[y + 1][i] |= go_south[i];
+            }
+        }
+
+        self.rows = rows;
+        self.grow();
+        moved
+    }
+
+    fn positions(&self) -> BTreeSet<Point> {
+        let mut positions = BTreeSet::new();
+        for (row, words) in self.rows.iter().enumerate() {
+            for (i, word) in words.iter().enumerate() {
+                for bit in (0..64).filter(|bit| word & (1 << bit) != 0) {
+                    let x = self.origin.0 + (i * 64 + bit) as i64;
+                    positions.insert(vec![x, self.origin.1 - row as i64]);
+                }
+            }
+        }
+        normalize(positions)
+    }
+
+    fn bounding_box(&self) -> (Point, Point) {
+        let occupied = |row: &&Vec<u64>| row.iter().any(|word| *word != 0);
+        if !self.rows.iter().any(|row| occupied(&row)) {
+            return (vec![0, 0], vec![0, 0]);
+        }
+        let height = self
+            .rows
+            .iter()
+            .rev()
+            .skip_while(|row| !occupied(row))
+            .count()
+            - self.rows.iter().take_while(|row| !occupied(row)).count();
+
+        let columns = self
+            .rows
+            .iter()
+            .fold(vec![0; self.rows[0].len()], |columns, row| {
+                columns.iter().zip(row.iter()).map(|(a, b)| a | b).collect()
+            });
+        let first = columns.iter().position(|word| *word != 0).unwrap_or(0);
+        let last = columns.iter().rposition(|word| *word != 0).unwrap_or(0);
+        let min_x = first * 64 + columns[first].trailing_zeros() as usize;
+        let max_x = last * 64 + 63 - columns[last].leading_zeros() as usize;
+
+        (vec![0, 0], vec![(max_x - min_x + 1) as i64, height as i64])
+    }
+}
+
+/// The `sets` engine runs any ruleset, while the `bits` engine only knows the
+/// day 23 rules and refuses anything else rather than quietly running them on
+/// sets.
+fn new_engine(
+    positions: BTreeSet<Point>,
+    ruleset: &Ruleset,
+    engine: &str,
+) -> eyre::Result<Box<dyn Engine>> {
+    match engine {
+        "sets" => Ok(Box::new(SetEngine {
+            positions,
+            ruleset: ruleset.clone(),
+        })),
+        "bits" if *ruleset == Ruleset::day23() => Ok(Box::new(BitEngine::new(&positions))),
+        "bits" => Err(eyre!("The bits engine only runs the day 23 rules")),
+        _ => Err(eyre!("Unknown engine {:?}, expected sets or bits", engine)),
+    }
+}
+
+/// What one round did to the field.
+struct RoundStats {
+    round: usize,
+    moved: usize,
+    area: i64,
+    empty_ground: i64,
+}
+
+/// Runs an `Engine` round by round, keeping stats on each one.
+pub struct Simulation {
+    engine: Box<dyn Engine>,
+    elves: usize,
+    stats: Vec<RoundStats>,
+}
+
+impl Simulation {
+    /// A simulation of the elves at `positions` following `ruleset`, on the
+    /// `sets` engine or the `bits` one.
+    pub fn new(
+        positions: BTreeSet<Point>,
+        ruleset: &Ruleset,
+        engine: &str,
+    ) -> eyre::Result<Simulation> {
+        Ok(Simulation {
+            elves: positions.len(),
+            engine: new_engine(positions, ruleset, engine)?,
+            stats: Vec::new(),
+        })
+    }
+
+    /// Plays the next round and returns how many elves moved.
+    pub fn step(&mut self) -> usize {
+        let moved = self.engine.round(self.stats.len());
+        let area = self.area();
+        self.stats.push(RoundStats {
+            round: self.stats.len() + 1,
+            moved,
+            area,
+            empty_ground: area - self.elves as i64,
+        });
+        moved
+    }
+
+    /// The smallest corner of the box around every elf and the corner one past
+    /// the largest.
+    pub fn bounding_box(&self) -> (Point, Point) {
+        self.engine.bounding_box()
+    }
+
+    /// The size of the bounding box.
+    pub fn area(&self) -> i64 {
+        let (min, max) = self.bounding_box();
+        min.iter()
+            .zip(max.iter())
+            .map(|(min, max)| max - min)
+            .product()
+    }
+
+    /// Spots inside the bounding box with no elf on them.
+    pub fn empty_ground(&self) -> i64 {
+        self.area() - self.elves as i64
+    }
+
+    /// Where every elf is now.
+    pub fn positions(&self) -> BTreeSet<Point> {
+        self.engine.positions()
+    }
+
+    /// Steps through `rounds` rounds and logs the map they leave behind.
+    pub fn run(&mut self, rounds: usize) {
+        for _ in 0..rounds {
+            self.step();
+        }
+        if log_enabled!(Level::Debug) {
+            log_map(&self.positions());
+        }
+    }
+
+    /// How many rounds have been played so far.
+    pub fn rounds(&self) -> usize {
+        self.stats.len()
+    }
+
+    /// Steps until a round where nobody moves and returns that round's number.
+    pub fn run_until_stable(&mut self) -> usize {
+        while self.step() > 0 {}
+        self.stats.len()
+    }
+
+    /// Writes the stats for every round played so far.
+    pub fn write_csv(&self, mut w: impl std::io::Write) -> std::io::Result<()> {
+        writeln!(w, "round,moved,area,empty_ground")?;
+        for stats in self.stats.iter() {
+            writeln!(
+                w,
+                "{},{},{},{}",
+                stats.round, stats.moved, stats.area, stats.empty_ground
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn normalize(positions: BTreeSet<Point>) -> BTreeSet<Point> {
+    let (min, _) = get_min_max(&positions);
+
+    positions
+        .into_iter()
+        .map(|position| {
+            position
+                .iter()
+                .zip(min.iter())
+                .map(|(p, m)| p - m)
+                .collect()
+        })
+        .collect()
+}
+
+pub fn parse(input: &Input) -> eyre::Result<BTreeSet<Point>> {
+    parse_positions(input.text(), Ruleset::day23().dimensions())
+        .ok_or(eyre!("Could not parse positions"))
+}
+
+fn day23_simulation(positions: &BTreeSet<Point>) -> Simulation {
+    Simulation::new(positions.clone(), &Ruleset::day23(), "bits")
+        .expect("the bits engine runs the day 23 rules")
+}
+
+/// Empty ground in the smallest box around the elves after 10 rounds.
+pub fn part_one(positions: &BTreeSet<Point>) -> i64 {
+    let mut simulation = day23_simulation(positions);
+    simulation.run(10);
+    simulation.empty_ground()
+}
+
+/// The first round where nobody moves.
+pub fn part_two(positions: &BTreeSet<Point>) -> usize {
+    day23_simulation(positions).run_until_stable()
+}
@@ -0,0 +1,59 @@
+use unstable_diffusion::{parse_positions, Ruleset, Simulation};
+
+/// Plays `field` on the set and bit engines side by side until nobody moves,
+/// checking they agree after every round.
+fn check_engines_agree(field: &str) {
+    let positions = parse_positions(field, 2).unwrap();
+    let ruleset = Ruleset::day23();
+    let mut sets = Simulation::new(positions.clone(), &ruleset, "sets").unwrap();
+    let mut bits = Simulation::new(positions, &ruleset, "bits").unwrap();
+    assert_eq!(sets.positions(), bits.positions(), "before round 1");
+
+    loop {
+        let moved = sets.step();
+        let round = sets.rounds();
+        assert_eq!(bits.step(), moved, "elves moved in round {round}");
+        assert_eq!(sets.positions(), bits.positions(), "after round {round}");
+        assert_eq!(
+            sets.bounding_box(),
+            bits.bounding_box(),
+            "after round {round}"
+        );
+        if moved == 0 {
+            break;
+        }
+        assert!(round < 1000, "still moving after {round} rounds");
+    }
+}
+
+#[test]
+fn engines_agree_on_the_example() {
+    check_engines_agree(include_str!("../examples/example.txt"));
+}
+
+#[test]
+fn engines_agree_with_elves_on_the_negative_edges() {
+    check_engines_agree(
+        "\
+##.#......
+#.........
+##........
+#.........
+.#........
+#.#.......
+###...#...
+##.##.....
+",
+    );
+}
+
+#[test]
+fn engines_agree_across_more_than_one_word() {
+    let field = format!(
+        "{}\n{}\n{}\n",
+        "#".repeat(70),
+        ".#".repeat(40),
+        "##.".repeat(30)
+    );
+    check_engines_agree(&field);
+}
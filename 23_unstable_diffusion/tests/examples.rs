@@ -0,0 +1,3 @@
+use unstable_diffusion::{parse, part_one, part_two};
+
+aoc::examples!(parse, part_one, part_two);
@@ -0,0 +1,99 @@
+use unstable_diffusion::{parse_positions, Ruleset, Simulation};
+
+const EXAMPLE: &str = include_str!("../examples/example.txt");
+
+/// Empty ground after 10 rounds and the first round nobody moves, on the
+/// example.
+fn run_example(ruleset: &Ruleset) -> (i64, usize) {
+    let positions = parse_positions(EXAMPLE, ruleset.dimensions()).unwrap();
+    let mut simulation = Simulation::new(positions.clone(), ruleset, "sets").unwrap();
+    simulation.run(10);
+    let empty_ground = simulation.empty_ground();
+    let mut simulation = Simulation::new(positions, ruleset, "sets").unwrap();
+    (empty_ground, simulation.run_until_stable())
+}
+
+fn parse_error(rules: &str) -> String {
+    Ruleset::parse(rules).err().unwrap().to_string()
+}
+
+#[test]
+fn day23_rules() {
+    let ruleset = Ruleset::day23();
+    assert_eq!(ruleset.dimensions(), 2);
+    assert_eq!(run_example(&ruleset), (110, 20));
+}
+
+#[test]
+fn hex_rules() {
+    let ruleset = Ruleset::parse(include_str!("../rules/hex.txt")).unwrap();
+    assert_eq!(ruleset.dimensions(), 2);
+    assert_eq!(run_example(&ruleset), (99, 13));
+}
+
+#[test]
+fn cube_rules() {
+    let ruleset = Ruleset::parse(include_str!("../rules/cube.txt")).unwrap();
+    assert_eq!(ruleset.dimensions(), 3);
+    assert_eq!(run_example(&ruleset), (518, 11));
+}
+
+#[test]
+fn only_the_day23_rules_run_on_bits() {
+    let positions = parse_positions(EXAMPLE, 2).unwrap();
+    let hex = Ruleset::parse(include_str!("../rules/hex.txt")).unwrap();
+    let e = Simulation::new(positions.clone(), &hex, "bits")
+        .err()
+        .unwrap();
+    assert_eq!(e.to_string(), "The bits engine only runs the day 23 rules");
+    assert!(Simulation::new(positions.clone(), &hex, "sets").is_ok());
+
+    let e = Simulation::new(positions, &Ruleset::day23(), "bit")
+        .err()
+        .unwrap();
+    assert_eq!(
+        e.to_string(),
+        "Unknown engine \"bit\", expected sets or bits"
+    );
+}
+
+#[test]
+fn unknown_lines_are_rejected() {
+    assert_eq!(
+        parse_error("# north first\nstep: 0,1\n"),
+        "line 2: unexpected \"step: 0,1\""
+    );
+}
+
+#[test]
+fn unknown_directions_are_rejected() {
+    assert_eq!(
+        parse_error("rule: north needs -1,1 0,1 1,1\n"),
+        "line 1: can't read offset \"north\""
+    );
+    assert_eq!(
+        parse_error("rule: 0,1 needs -1,1 0,1 1,one\n"),
+        "line 1: can't read offset \"1,one\""
+    );
+}
+
+#[test]
+fn empty_rules_are_rejected() {
+    assert_eq!(
+        parse_error("rule:\n"),
+        "line 1: expected \"rule: STEP needs ...\""
+    );
+    assert_eq!(parse_error("# nothing but comments\n\n"), "no rules given");
+}
+
+#[test]
+fn bad_neighbourhoods_are_rejected() {
+    assert_eq!(
+        parse_error("neighbours: 0,1 1,x\nrule: 0,1 needs 0,1\n"),
+        "line 1: can't read offset \"1,x\""
+    );
+    assert_eq!(
+        parse_error("neighbours: 0,1,0\nrule: 0,1 needs 0,1\n"),
+        "offset [0, 1, 0] doesn't have 2 coordinates"
+    );
+}
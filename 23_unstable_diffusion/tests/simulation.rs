@@ -0,0 +1,100 @@
+use unstable_diffusion::{parse_positions, Ruleset, Simulation};
+
+/// The small example from the puzzle, then how it looks after each round.
+const SMALL: [&str; 4] = [
+    "\
+.....
+..##.
+..#..
+.....
+..##.
+.....
+",
+    "\
+..##.
+.....
+..#..
+...#.
+..#..
+.....
+",
+    "\
+.....
+..##.
+.#...
+....#
+.....
+..#..
+",
+    "\
+..#..
+....#
+#....
+....#
+.....
+..#..
+",
+];
+
+fn small(engine: &str) -> Simulation {
+    let positions = parse_positions(SMALL[0], 2).unwrap();
+    Simulation::new(positions, &Ruleset::day23(), engine).unwrap()
+}
+
+#[test]
+fn small_example_round_by_round() {
+    for engine in ["sets", "bits"] {
+        let mut simulation = small(engine);
+        let rounds = [(3, 2, 5), (5, 4, 5), (3, 5, 6)];
+        for (round, (field, (moved, width, height))) in SMALL[1..].iter().zip(rounds).enumerate() {
+            assert_eq!(simulation.step(), moved, "{engine} round {}", round + 1);
+            assert_eq!(
+                simulation.positions(),
+                parse_positions(field, 2).unwrap(),
+                "{engine} round {}",
+                round + 1
+            );
+            assert_eq!(
+                simulation.bounding_box(),
+                (vec![0, 0], vec![width, height]),
+                "{engine} round {}",
+                round + 1
+            );
+            assert_eq!(simulation.area(), width * height);
+            assert_eq!(simulation.empty_ground(), width * height - 5);
+        }
+        assert_eq!(simulation.rounds(), 3);
+    }
+}
+
+#[test]
+fn small_example_settles_in_round_four() {
+    for engine in ["sets", "bits"] {
+        let mut simulation = small(engine);
+        assert_eq!(simulation.run_until_stable(), 4, "{engine}");
+        assert_eq!(simulation.rounds(), 4);
+        assert_eq!(
+            simulation.positions(),
+            parse_positions(SMALL[3], 2).unwrap()
+        );
+    }
+}
+
+#[test]
+fn csv_has_a_row_per_round() {
+    let mut simulation = small("bits");
+    simulation.run(3);
+    let mut csv = Vec::new();
+    simulation.write_csv(&mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            "round,moved,area,empty_ground",
+            "1,3,10,5",
+            "2,5,20,15",
+            "3,3,30,25",
+        ]
+    );
+}
@@ -0,0 +1,20 @@
+use blizzard_basin::{parse, part_one, part_two};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn parts(c: &mut Criterion) {
+    aoc::bench::parts(
+        c,
+        "24_blizzard-basin",
+        include_str!("../input.txt"),
+        parse,
+        part_one,
+        part_two,
+    );
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = parts
+}
+criterion_main!(benches);
@@ -0,0 +1,536 @@
+//! Day 24: Blizzard Basin.
+
+use std::collections::{HashMap, HashSet};
+
+use aoc::{Input, InputError};
+use eyre::eyre;
+
+use derive_more::{Add, AddAssign, Rem, Sub};
+
+#[derive(Add, AddAssign, Sub, Rem, Copy, Clone, Debug, Hash, Eq, PartialEq, PartialOrd, Ord)]
+pub struct Minute(pub usize);
+
+#[derive(Add, Sub, Copy, Clone, Debug, Hash, Eq, PartialEq, PartialOrd, Ord)]
+pub struct Location {
+    pub col: usize,
+    pub row: usize,
+}
+
+pub type Valley = Vec<Vec<char>>;
+
+enum Traveling {
+    North,
+    South,
+    East,
+    West,
+}
+
+pub struct Blizzard {
+    origin_row: usize,
+    origin_col: usize,
+    traveling: Traveling,
+}
+
+impl Blizzard {
+    fn new(col: usize, row: usize, traveling: Traveling) -> Blizzard {
+        Blizzard {
+            origin_col: col - 1,
+            origin_row: row - 1,
+            traveling,
+        }
+    }
+
+    fn location(&self, minute: Minute, valley: &Valley) -> Location {
+        let innerh = valley.len() - 2;
+        let innerw = valley[0].len() - 2;
+        let minuteh = minute.0 % innerh;
+        let minutew = minute.0 % innerw;
+
+        match self.traveling {
+            Traveling::North => Location {
+                col: self.origin_col + 1,
+                row: (innerh + self.origin_row - minuteh) % innerh + 1,
+            },
+            Traveling::South => Location {
+                col: self.origin_col + 1,
+                row: (innerh + self.origin_row + minuteh) % innerh + 1,
+            },
+            Traveling::East => Location {
+                col: (innerw + self.origin_col + minutew) % innerw + 1,
+                row: self.origin_row + 1,
+            },
+            Traveling::West => Location {
+                col: (innerw + self.origin_col - minutew) % innerw + 1,
+                row: self.origin_row + 1,
+            },
+        }
+    }
+
+    fn as_char(&self) -> char {
+        match self.traveling {
+            Traveling::North => '^',
+            Traveling::South => 'v',
+            Traveling::East => '>',
+            Traveling::West => '<',
+        }
+    }
+}
+
+fn parse_map(input: &Input) -> Result<(Vec<Blizzard>, Vec<Vec<char>>), InputError> {
+    let mut valley = Valley::new();
+    let mut blizzards = Vec::new();
+
+    for (row, line) in input.lines().enumerate() {
+        let mut valley_row = Vec::new();
+        for (col, char) in line.chars().enumerate() {
+            match char {
+                '#' => valley_row.push('#'),
+                '.' => valley_row.push('.'),
+                '^' => {
+                    valley_row.push('.');
+                    blizzards.push(Blizzard::new(col, row, Traveling::North))
+                }
+                '>' => {
+                    valley_row.push('.');
+                    blizzards.push(Blizzard::new(col, row, Traveling::East))
+                }
+                'v' => {
+                    valley_row.push('.');
+                    blizzards.push(Blizzard::new(col, row, Traveling::South))
+                }
+                '<' => {
+                    valley_row.push('.');
+                    blizzards.push(Blizzard::new(col, row, Traveling::West))
+                }
+                c => {
+                    return Err(InputError {
+                        column: Some(col + 1),
+                        ..line.error(format!("unknown character in map: {c:?}"))
+                    })
+                }
+            }
+        }
+        valley.push(valley_row);
+    }
+
+    Ok((blizzards, valley))
+}
+
+/// Draws the valley at `minute`, with the expedition as `E` if it's given.
+fn render_map(
+    valley: &Valley,
+    blizzards: &[Blizzard],
+    minute: Minute,
+    expedition: Option<Location>,
+) -> String {
+    let h = valley.len();
+    let w = valley[0].len();
+
+    let mut blizzard_locations: Vec<Vec<Vec<char>>> = vec![vec![Vec::new(); w]; h];
+
+    for blizzard in blizzards.iter() {
+        let location = blizzard.location(minute, valley);
+        blizzard_locations[location.row][location.col].push(blizzard.as_char());
+    }
+
+    let mut map = format!("Minute: {}\n", minute.0);
+    for (row, valley_row) in valley.iter().enumerate() {
+        for (col, valley_spot) in valley_row.iter().enumerate() {
+            if valley_spot == &'#' {
+                map.push('#');
+            } else if expedition == Some(Location { col, row }) {
+                map.push('E');
+            } else if blizzard_locations[row][col].len() > 1 {
+                map += &blizzard_locations[row][col].len().to_string();
+            } else if blizzard_locations[row][col].len() == 1 {
+                map.push(blizzard_locations[row][col][0]);
+            } else {
+                map.push('.');
+            }
+        }
+        map.push('\n');
+    }
+    map
+}
+
+/// One rendered frame per minute of `trip`, from where it starts to where it
+/// ends up.
+pub fn trip_frames(
+    trip: &Trip,
+    start: Location,
+    start_minute: Minute,
+    blizzards: &[Blizzard],
+    valley: &Valley,
+) -> Vec<String> {
+    let mut location = start;
+    let mut frames = vec![render_map(valley, blizzards, start_minute, Some(location))];
+    for (i, m) in trip.moves.iter().enumerate() {
+        location = m.apply(location);
+        let minute = start_minute + Minute(i + 1);
+        frames.push(render_map(valley, blizzards, minute, Some(location)));
+    }
+    frames
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+fn lcm(a: usize, b: usize) -> usize {
+    a / gcd(a, b) * b
+}
+
+/// A bitset spread over as many `u64` words as it needs.
+type Bits = Vec<u64>;
+
+/// `bits` with every bit moved `by` places towards the high end.
+fn shift_up(bits: &[u64], by: usize) -> Bits {
+    let (words, shift) = (by / 64, by % 64);
+    (0..bits.len())
+        .map(|i| {
+            let low = i.checked_sub(words).map_or(0, |j| bits[j] << shift);
+            let carry = match i.checked_sub(words + 1) {
+                Some(j) if shift > 0 => bits[j] >> (64 - shift),
+                _ => 0,
+            };
+            low | carry
+        })
+        .collect()
+}
+
+/// `bits` with every bit moved `by` places towards the low end.
+fn shift_down(bits: &[u64], by: usize) -> Bits {
+    let (words, shift) = (by / 64, by % 64);
+    (0..bits.len())
+        .map(|i| {
+            let high = bits.get(i + words).map_or(0, |word| word >> shift);
+            let carry = match bits.get(i + words + 1) {
+                Some(word) if shift > 0 => word << (64 - shift),
+                _ => 0,
+            };
+            high | carry
+        })
+        .collect()
+}
+
+/// The first `len` bits of `bits` rotated `by` places towards the high end,
+/// so bit `i` ends up at `(i + by) % len`.
+fn rotate(bits: &[u64], len: usize, by: usize) -> Bits {
+    let by = by % len;
+    let mut rotated: Bits = shift_up(bits, by)
+        .iter()
+        .zip(shift_down(bits, len - by))
+        .map(|(up, down)| up | down)
+        .collect();
+    if !len.is_multiple_of(64) {
+        *rotated.last_mut().unwrap() &= (1 << (len % 64)) - 1;
+    }
+    rotated
+}
+
+fn is_set(bits: &[u64], i: usize) -> bool {
+    bits.get(i / 64)
+        .is_some_and(|word| word & (1 << (i % 64)) != 0)
+}
+
+/// Which cells of the valley's interior have a blizzard in them, at any
+/// minute. Blizzards only ever move along their own row or column, so each
+/// row's east and west blizzards are a bitset shifted round the row once per
+/// minute, and each column's north and south blizzards one shifted round the
+/// column. Both are kept for every minute until they repeat.
+pub struct BlizzardMap {
+    /// The `lcm(width, height)` minutes after which every blizzard is back
+    /// where it started.
+    period: usize,
+    /// `rows[minute % width][row - 1]` has bit `col - 1` set when occupied.
+    rows: Vec<Vec<Bits>>,
+    /// `cols[minute % height][col - 1]` has bit `row - 1` set when occupied.
+    cols: Vec<Vec<Bits>>,
+}
+
+impl BlizzardMap {
+    pub fn new(blizzards: &[Blizzard], valley: &Valley) -> BlizzardMap {
+        let innerh = valley.len() - 2;
+        let innerw = valley[0].len() - 2;
+        let period = lcm(innerw, innerh);
+
+        // Where the blizzards start, one mask per row for those moving along
+        // it and one per column for those moving up or down it.
+        let mut east = vec![vec![0; innerw.div_ceil(64)]; innerh];
+        let mut west = east.clone();
+        let mut north = vec![vec![0; innerh.div_ceil(64)]; innerw];
+        let mut south = north.clone();
+        for blizzard in blizzards.iter() {
+            let (row, col) = (blizzard.origin_row, blizzard.origin_col);
+            let (bits, i) = match blizzard.traveling {
+                Traveling::East => (&mut east[row], col),
+                Traveling::West => (&mut west[row], col),
+                Traveling::North => (&mut north[col], row),
+                Traveling::South => (&mut south[col], row),
+            };
+            bits[i / 64] |= 1 << (i % 64);
+        }
+
+        // East and south blizzards shift up by a minute at a time, west and
+        // north ones shift down, which is up by the rest of the way round.
+        let combine = |forward: &[Bits], backward: &[Bits], len: usize, minute: usize| {
+            forward
+                .iter()
+                .zip(backward.iter())
+                .map(|(forward, backward)| {
+                    rotate(forward, len, minute)
+                        .iter()
+                        .zip(rotate(backward, len, len - minute))
+                        .map(|(f, b)| f | b)
+                        .collect()
+                })
+                .collect()
+        };
+        let rows = (0..innerw)
+            .map(|minute| combine(&east, &west, innerw, minute))
+            .collect();
+        let cols = (0..innerh)
+            .map(|minute| combine(&south, &north, innerh, minute))
+            .collect();
+
+        BlizzardMap { period, rows, cols }
+    }
+
+    /// Whether a blizzard is at `location` at `minute`. The start and end gaps
+    /// in the wall are never occupied.
+    fn is_occupied(&self, minute: Minute, location: Location) -> bool {
+        let rows = &self.rows[minute.0 % self.rows.len()];
+        if location.row == 0 || location.row > rows.len() || location.col == 0 {
+            return false;
+        }
+        let (row, col) = (location.row - 1, location.col - 1);
+        let cols = &self.cols[minute.0 % self.cols.len()];
+        is_set(&rows[row], col) || cols.get(col).is_some_and(|bits| is_set(bits, row))
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Move {
+    Wait,
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Move {
+    fn apply(&self, location: Location) -> Location {
+        match self {
+            Move::Wait => location,
+            Move::Up => location - Location { col: 0, row: 1 },
+            Move::Down => location + Location { col: 0, row: 1 },
+            Move::Left => location - Location { col: 1, row: 0 },
+            Move::Right => location + Location { col: 1, row: 0 },
+        }
+    }
+}
+
+/// Breadth first search from `start` at `minute` to the earliest minute the
+/// expedition can stand on `end`, returning that minute and the moves made.
+/// `None` if the blizzards cut `end` off for good.
+fn find_path(
+    mut minute: Minute,
+    start: Location,
+    end: Location,
+    blizzard_map: &BlizzardMap,
+    valley: &Valley,
+) -> Option<(Minute, Vec<Move>)> {
+    let period = blizzard_map.period;
+    let first_minute = minute;
+
+    let mut locations: HashSet<Location> = HashSet::new();
+    locations.insert(start);
+
+    let mut seen_states: HashSet<(Minute, Location)> = HashSet::new();
+    seen_states.insert((minute % period, start));
+
+    // Where each (minute, location) was reached from, and how.
+    let mut came_from: HashMap<(Minute, Location), (Location, Move)> = HashMap::new();
+
+    while !locations.is_empty() {
+        if locations.contains(&end) {
+            let mut moves = Vec::new();
+            let mut location = end;
+            while minute > first_minute {
+                let (previous, m) = came_from[&(minute, location)];
+                moves.push(m);
+                location = previous;
+                minute = minute - Minute(1);
+            }
+            moves.reverse();
+            return Some((first_minute + Minute(moves.len()), moves));
+        }
+
+        let mut next_locations: HashSet<Location> = HashSet::new();
+
+        let next_minute = minute + Minute(1);
+
+        for location in locations.iter() {
+            let mut candidates = vec![Move::Wait, Move::Left, Move::Right];
+            if location.row != 0 {
+                candidates.push(Move::Up);
+            }
+            if location.row != valley.len() - 1 {
+                candidates.push(Move::Down);
+            }
+
+            for m in candidates {
+                let next_location = m.apply(*location);
+                if valley[next_location.row][next_location.col] != '#'
+                    && !blizzard_map.is_occupied(next_minute, next_location)
+                    && seen_states.insert((next_minute % period, next_location))
+                {
+                    next_locations.insert(next_location);
+                    came_from.insert((next_minute, next_location), (*location, m));
+                }
+            }
+        }
+
+        minute = next_minute;
+        locations = next_locations;
+    }
+    None
+}
+
+/// A route through the valley visiting waypoints in order.
+pub struct Trip {
+    /// The minute each waypoint after the first was reached.
+    pub arrivals: Vec<Minute>,
+    /// One move per minute from the start of the trip to the last arrival.
+    pub moves: Vec<Move>,
+}
+
+/// Plans a trip starting at `waypoints[0]` at `start_minute` and then going
+/// to each of the other waypoints in turn, as early as possible.
+pub fn plan_trip(
+    waypoints: &[Location],
+    start_minute: Minute,
+    blizzard_map: &BlizzardMap,
+    valley: &Valley,
+) -> Option<Trip> {
+    let mut trip = Trip {
+        arrivals: Vec::new(),
+        moves: Vec::new(),
+    };
+    let mut minute = start_minute;
+    for leg in waypoints.windows(2) {
+        let (arrival, moves) = find_path(minute, leg[0], leg[1], blizzard_map, valley)?;
+        trip.arrivals.push(arrival);
+        trip.moves.extend(moves);
+        minute = arrival;
+    }
+    Some(trip)
+}
+
+/// Reads `start`, `end` or `ROW:COL`.
+pub fn parse_waypoint(waypoint: &str, start: Location, end: Location) -> eyre::Result<Location> {
+    match waypoint {
+        "start" => Ok(start),
+        "end" => Ok(end),
+        _ => {
+            let (row, col) = waypoint
+                .split_once(':')
+                .ok_or(eyre!("Expected start, end or ROW:COL, not {}", waypoint))?;
+            Ok(Location {
+                row: row.parse()?,
+                col: col.parse()?,
+            })
+        }
+    }
+}
+
+/// The valley, its blizzards and where the expedition comes in and goes out.
+pub struct Basin {
+    pub blizzards: Vec<Blizzard>,
+    pub valley: Valley,
+    pub blizzard_map: BlizzardMap,
+    pub start: Location,
+    pub end: Location,
+}
+
+impl Basin {
+    /// Whether `location` is inside the valley and not a wall.
+    pub fn is_open(&self, location: Location) -> bool {
+        self.valley
+            .get(location.row)
+            .and_then(|row| row.get(location.col))
+            .is_some_and(|spot| *spot != '#')
+    }
+
+    /// Reads a comma separated list of waypoints, see `parse_waypoint`.
+    pub fn parse_trip(&self, trip: &str) -> eyre::Result<Vec<Location>> {
+        trip.split(',')
+            .map(|waypoint| parse_waypoint(waypoint, self.start, self.end))
+            .collect()
+    }
+
+    /// Plans a trip through `waypoints` from minute 0, as long as they're all
+    /// open ground.
+    pub fn plan(&self, waypoints: &[Location]) -> eyre::Result<Trip> {
+        for waypoint in waypoints.iter() {
+            if !self.is_open(*waypoint) {
+                return Err(eyre!("{:?} is not open ground", waypoint));
+            }
+        }
+        plan_trip(waypoints, Minute(0), &self.blizzard_map, &self.valley)
+            .ok_or(eyre!("The blizzards never let the expedition through"))
+    }
+
+    /// One rendered frame per minute of a trip planned from `start`.
+    pub fn frames(&self, trip: &Trip, start: Location) -> Vec<String> {
+        trip_frames(trip, start, Minute(0), &self.blizzards, &self.valley)
+    }
+
+    /// How many minutes it takes to visit `waypoints` in order.
+    pub fn trip_minutes(&self, waypoints: &[Location]) -> Option<usize> {
+        plan_trip(waypoints, Minute(0), &self.blizzard_map, &self.valley)
+            .map(|trip| trip.moves.len())
+    }
+}
+
+pub fn parse(input: &Input) -> Result<Basin, InputError> {
+    let (blizzards, valley) = parse_map(input)?;
+    let opening = |row: usize| {
+        valley[row]
+            .iter()
+            .position(|p| p == &'.')
+            .ok_or_else(|| input.error(row + 1, "the valley has no opening in this wall"))
+    };
+    if valley.is_empty() {
+        return Err(input.error(1, "the valley is empty"));
+    }
+    let start = Location {
+        row: 0,
+        col: opening(0)?,
+    };
+    let end = Location {
+        row: valley.len() - 1,
+        col: opening(valley.len() - 1)?,
+    };
+    let blizzard_map = BlizzardMap::new(&blizzards, &valley);
+
+    Ok(Basin {
+        blizzards,
+        valley,
+        blizzard_map,
+        start,
+        end,
+    })
+}
+
+pub fn part_one(basin: &Basin) -> Option<usize> {
+    basin.trip_minutes(&[basin.start, basin.end])
+}
+
+pub fn part_two(basin: &Basin) -> Option<usize> {
+    basin.trip_minutes(&[basin.start, basin.end, basin.start, basin.end])
+}
@@ -0,0 +1,3 @@
+use blizzard_basin::{parse, part_one, part_two};
+
+aoc::examples!(parse, part_one, part_two);
@@ -0,0 +1,67 @@
+use aoc::Input;
+use blizzard_basin::{parse, Basin, Minute};
+
+fn example() -> Basin {
+    parse(&Input::new(
+        "example.txt",
+        include_str!("../examples/example.txt"),
+    ))
+    .unwrap()
+}
+
+fn arrivals(basin: &Basin, trip: &str) -> Vec<usize> {
+    let waypoints = basin.parse_trip(trip).unwrap();
+    let trip = basin.plan(&waypoints).unwrap();
+    assert_eq!(trip.moves.len(), trip.arrivals.last().unwrap().0);
+    trip.arrivals.iter().map(|Minute(minute)| *minute).collect()
+}
+
+#[test]
+fn start_to_end() {
+    assert_eq!(arrivals(&example(), "start,end"), [18]);
+}
+
+#[test]
+fn there_back_and_there_again() {
+    assert_eq!(arrivals(&example(), "start,end,start,end"), [18, 41, 54]);
+}
+
+#[test]
+fn through_a_waypoint_in_the_valley() {
+    let basin = example();
+    assert_eq!(arrivals(&basin, "start,4:1,end"), [16, 29]);
+    assert_eq!(arrivals(&basin, "start,3:3,end"), [13, 18]);
+}
+
+#[test]
+fn waypoints_have_to_be_open_ground() {
+    let basin = example();
+    let waypoints = basin.parse_trip("start,0:0,end").unwrap();
+    let e = basin.plan(&waypoints).err().unwrap();
+    assert_eq!(
+        e.to_string(),
+        "Location { col: 0, row: 0 } is not open ground"
+    );
+}
+
+#[test]
+fn waypoints_are_start_end_or_row_and_col() {
+    let basin = example();
+    assert!(basin.parse_trip("start,middle").is_err());
+    assert!(basin.parse_trip("start,1:x").is_err());
+}
+
+#[test]
+fn frames_follow_the_expedition_every_minute() {
+    let basin = example();
+    let waypoints = basin.parse_trip("start,end").unwrap();
+    let trip = basin.plan(&waypoints).unwrap();
+    let frames = basin.frames(&trip, basin.start);
+
+    assert_eq!(frames.len(), 19);
+    for frame in frames.iter() {
+        assert_eq!(frame.matches('E').count(), 1, "{frame}");
+    }
+    assert!(frames[0].contains("\n#E######\n"), "{}", frames[0]);
+    assert!(frames[18].ends_with("######E#\n"), "{}", frames[18]);
+}
@@ -0,0 +1,35 @@
+//! Criterion benchmarks of a day's parse and parts, run from each day's
+//! `benches/parts.rs`.
+
+use std::{borrow::Borrow, fmt::Debug, hint::black_box};
+
+use criterion::Criterion;
+
+use crate::Input;
+
+/// Benchmarks `parse`, `part_one` and `part_two` separately over `raw`, in a
+/// group called `day`. The parts are given the same parsed input each time,
+/// borrowed as whatever they take, so a part can take a `&[T]` of a `Vec<T>`.
+/// No logger is set up, so the parts' progress events cost next to nothing and
+/// don't bury criterion's report.
+pub fn parts<P: Borrow<Q>, Q: ?Sized, E: Debug, A, B>(
+    c: &mut Criterion,
+    day: &str,
+    raw: &str,
+    parse: impl Fn(&Input) -> Result<P, E>,
+    part_one: impl Fn(&Q) -> A,
+    part_two: impl Fn(&Q) -> B,
+) {
+    let input = Input::new("input.txt", raw);
+    let parsed = parse(&input).expect("input.txt parses");
+
+    let mut group = c.benchmark_group(day);
+    group.bench_function("parse", |b| b.iter(|| parse(black_box(&input))));
+    group.bench_function("part one", |b| {
+        b.iter(|| part_one(black_box(parsed.borrow())))
+    });
+    group.bench_function("part two", |b| {
+        b.iter(|| part_two(black_box(parsed.borrow())))
+    });
+    group.finish();
+}
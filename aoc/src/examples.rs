@@ -0,0 +1,163 @@
+//! Tests generated from the example fixtures in each day's `examples/`
+//! directory. A fixture is a pair of files, `NAME.txt` with the puzzle input
+//! and `NAME.answers` with the answers it should give:
+//!
+//! ```text
+//! part one: 24000
+//! part two: 45000
+//! ```
+//!
+//! Either answer can be left out when the fixture only covers one part. A
+//! day's `build.rs` calls `generate_tests` and its `tests/examples.rs` calls
+//! `examples!` with the day's parse and parts.
+
+use std::{borrow::Borrow, fmt::Debug, fmt::Write, path::Path};
+
+use crate::Input;
+
+/// How an answer is written down in a `.answers` file.
+pub trait Answer {
+    fn answer(&self) -> String;
+}
+
+macro_rules! display_answer {
+    ($($t:ty),*) => {
+        $(impl Answer for $t {
+            fn answer(&self) -> String {
+                self.to_string()
+            }
+        })*
+    };
+}
+
+display_answer!(i32, i64, u32, u64, usize, String, &str);
+
+/// A part that can come up empty answers `none`.
+impl<T: Answer> Answer for Option<T> {
+    fn answer(&self) -> String {
+        match self {
+            Some(answer) => answer.answer(),
+            None => "none".to_owned(),
+        }
+    }
+}
+
+impl<T: Answer, E: Debug> Answer for Result<T, E> {
+    fn answer(&self) -> String {
+        match self {
+            Ok(answer) => answer.answer(),
+            Err(e) => format!("error: {e:?}"),
+        }
+    }
+}
+
+/// Parts that answer once per line of input are written comma separated.
+impl<T: Answer> Answer for Vec<T> {
+    fn answer(&self) -> String {
+        self.iter()
+            .map(Answer::answer)
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+/// Parses `input` and checks each part against the answer given for it in
+/// `answers`.
+pub fn check<P: Borrow<Q>, Q: ?Sized, E: Debug, A: Answer, B: Answer>(
+    name: &str,
+    input: &str,
+    answers: &str,
+    parse: impl Fn(&Input) -> Result<P, E>,
+    part_one: impl Fn(&Q) -> A,
+    part_two: impl Fn(&Q) -> B,
+) {
+    let mut expected = [None, None];
+    for line in answers.lines().filter(|line| !line.trim().is_empty()) {
+        match line.split_once(':') {
+            Some(("part one", answer)) => expected[0] = Some(answer.trim()),
+            Some(("part two", answer)) => expected[1] = Some(answer.trim()),
+            _ => {
+                panic!("{name}.answers: expected `part one: ...` or `part two: ...`, not {line:?}")
+            }
+        }
+    }
+    assert!(
+        expected.iter().any(Option::is_some),
+        "{name}.answers doesn't give any answers"
+    );
+
+    let input = Input::new(&format!("{name}.txt"), input);
+    let parsed = parse(&input).unwrap_or_else(|e| panic!("{name}.txt doesn't parse: {e:?}"));
+    if let Some(answer) = expected[0] {
+        assert_eq!(
+            part_one(parsed.borrow()).answer(),
+            answer,
+            "part one of {name}"
+        );
+    }
+    if let Some(answer) = expected[1] {
+        assert_eq!(
+            part_two(parsed.borrow()).answer(),
+            answer,
+            "part two of {name}"
+        );
+    }
+}
+
+/// Writes a `#[test]` for every fixture in the package's `examples/`
+/// directory to `$OUT_DIR/examples.rs`, for `examples!` to include. Meant to be
+/// called from a build script.
+pub fn generate_tests() {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").expect("run from a build script");
+    let out_dir = std::env::var("OUT_DIR").expect("run from a build script");
+    let examples = Path::new(&manifest_dir).join("examples");
+    println!("cargo:rerun-if-changed={}", examples.display());
+
+    let mut inputs: Vec<_> = std::fs::read_dir(&examples)
+        .map(|entries| {
+            entries
+                .map(|entry| entry.expect("examples/ can be listed").path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+                .collect()
+        })
+        .unwrap_or_default();
+    inputs.sort();
+
+    let mut tests = String::new();
+    for input in inputs {
+        let name = input.file_stem().unwrap().to_string_lossy().into_owned();
+        let answers = input.with_extension("answers");
+        assert!(
+            answers.exists(),
+            "examples/{name}.txt has no examples/{name}.answers next to it"
+        );
+
+        let mut test = name.replace(|c: char| !c.is_ascii_alphanumeric(), "_");
+        if test.starts_with(|c: char| c.is_ascii_digit()) {
+            test.insert_str(0, "example_");
+        }
+        writeln!(
+            tests,
+            "#[test]\nfn {test}() {{\n    check_example({name:?}, include_str!({:?}), include_str!({:?}));\n}}\n",
+            input.display(),
+            answers.display()
+        )
+        .unwrap();
+    }
+
+    std::fs::write(Path::new(&out_dir).join("examples.rs"), tests)
+        .expect("examples.rs can be written to OUT_DIR");
+}
+
+/// Includes the tests `generate_tests` wrote, each checking its fixture with
+/// the given parse and parts.
+#[macro_export]
+macro_rules! examples {
+    ($parse:expr, $part_one:expr, $part_two:expr) => {
+        fn check_example(name: &str, input: &str, answers: &str) {
+            $crate::examples::check(name, input, answers, $parse, $part_one, $part_two);
+        }
+
+        include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+    };
+}
@@ -0,0 +1,254 @@
+use std::{fmt::Display, io::Read, ops::Deref, str::FromStr};
+
+use crate::parse::{end, terminated, PResult};
+
+/// A puzzle input, read from a file, stdin or an example compiled into the
+/// binary. Lines have their trailing whitespace and CRs stripped, and
+/// trailing blank lines are dropped, so every line ends in a single `\n`.
+pub struct Input {
+    name: String,
+    text: String,
+}
+
+/// Something wrong with the input, at a given line, and maybe column, of a
+/// given file.
+#[derive(Debug)]
+pub struct InputError {
+    pub name: String,
+    pub line: usize,
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:", self.name, self.line)?;
+        if let Some(column) = self.column {
+            write!(f, "{column}:")?;
+        }
+        write!(f, " {}", self.message)
+    }
+}
+
+impl std::error::Error for InputError {}
+
+fn normalize(raw: &str) -> String {
+    let mut text = String::with_capacity(raw.len());
+    for line in raw.lines() {
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+    let len = text.trim_end().len();
+    text.truncate(len);
+    if !text.is_empty() {
+        text.push('\n');
+    }
+    text
+}
+
+impl Input {
+    pub fn new(name: &str, raw: &str) -> Input {
+        Input {
+            name: name.to_owned(),
+            text: normalize(raw),
+        }
+    }
+
+    /// Read a file, or stdin when the path is `-`.
+    pub fn from_path(path: &str) -> std::io::Result<Input> {
+        if path == "-" {
+            let mut raw = String::new();
+            std::io::stdin().read_to_string(&mut raw)?;
+            return Ok(Input::new("<stdin>", &raw));
+        }
+
+        let raw = std::fs::read_to_string(path)
+            .map_err(|e| std::io::Error::new(e.kind(), format!("{path}: {e}")))?;
+        Ok(Input::new(path, &raw))
+    }
+
+    /// The one argument that isn't a `--flag` or one of the `-q`/`-v`
+    /// verbosity flags, `-` for stdin, or `input.txt` if there isn't one.
+    /// Options take their value as `--name=value`, so `--name value` leaves
+    /// a stray argument and is rejected rather than read as the path.
+    pub fn path_from_args(args: &[String]) -> std::io::Result<&str> {
+        let mut paths = args
+            .iter()
+            .filter(|arg| arg.as_str() == "-" || !arg.starts_with('-'));
+        match (paths.next(), paths.next()) {
+            (None, _) => Ok("input.txt"),
+            (Some(path), None) => Ok(path),
+            (Some(first), Some(second)) => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!(
+                    "expected one input path, found '{first}' and '{second}' \
+                     (options take their value as --name=value)"
+                ),
+            )),
+        }
+    }
+
+    /// Read the input named by `path_from_args` in the command line.
+    pub fn from_args() -> std::io::Result<Input> {
+        let args: Vec<String> = std::env::args().skip(1).collect();
+        Input::from_path(Input::path_from_args(&args)?)
+    }
+
+    /// Like `from_args`, but `--example` reads the given example instead.
+    pub fn from_args_with_example(example: &str) -> std::io::Result<Input> {
+        if std::env::args().any(|arg| arg == "--example") {
+            return Ok(Input::new("<example>", example));
+        }
+        Input::from_args()
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = Line<'_>> {
+        numbered_lines(&self.name, &self.text, 1)
+    }
+
+    /// Runs of non-blank lines, separated by one or more blank lines.
+    pub fn paragraphs(&self) -> impl Iterator<Item = Paragraph<'_>> {
+        let mut lines = self.lines().peekable();
+        std::iter::from_fn(move || {
+            while lines.next_if(|line| line.is_empty()).is_some() {}
+            let first = lines.next()?;
+            let mut end = first.offset + first.text.len();
+            while let Some(line) = lines.next_if(|line| !line.is_empty()) {
+                end = line.offset + line.text.len();
+            }
+            Some(Paragraph {
+                name: &self.name,
+                number: first.number,
+                text: &self.text[first.offset..end],
+            })
+        })
+    }
+
+    pub fn error(&self, line: usize, message: impl Display) -> InputError {
+        InputError {
+            name: self.name.clone(),
+            line,
+            column: None,
+            message: message.to_string(),
+        }
+    }
+}
+
+fn numbered_lines<'a>(
+    name: &'a str,
+    text: &'a str,
+    first: usize,
+) -> impl Iterator<Item = Line<'a>> {
+    let mut offset = 0;
+    text.lines().enumerate().map(move |(i, line)| {
+        let line = Line {
+            name,
+            number: first + i,
+            offset,
+            text: line,
+        };
+        offset += line.text.len() + 1;
+        line
+    })
+}
+
+/// One line of the input, which knows where it came from.
+#[derive(Copy, Clone, Debug)]
+pub struct Line<'a> {
+    name: &'a str,
+    pub number: usize,
+    offset: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    pub fn error(&self, message: impl Display) -> InputError {
+        InputError {
+            name: self.name.to_owned(),
+            line: self.number,
+            column: None,
+            message: message.to_string(),
+        }
+    }
+
+    /// Run a parser over the whole line, reporting where it failed or where
+    /// it stopped short of the end.
+    pub fn parse_with<T>(
+        &self,
+        parser: impl Fn(&'a str) -> PResult<'a, T>,
+    ) -> Result<T, InputError> {
+        terminated(parser, end())(self.text)
+            .map(|(_, value)| value)
+            .map_err(|e| {
+                let column = self.text.len() - e.remaining + 1;
+                let found = match &self.text[column - 1..] {
+                    "" => "the end of the line".to_owned(),
+                    rest => format!("{rest:?}"),
+                };
+                InputError {
+                    name: self.name.to_owned(),
+                    line: self.number,
+                    column: Some(column),
+                    message: format!("{}, found {found}", e.expected),
+                }
+            })
+    }
+
+    /// Parse the whole line, reporting failures against it.
+    pub fn parse<T>(&self) -> Result<T, InputError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.text
+            .parse()
+            .map_err(|e| self.error(format!("{e}: {:?}", self.text)))
+    }
+}
+
+impl Deref for Line<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.text
+    }
+}
+
+/// A run of non-blank lines.
+#[derive(Copy, Clone, Debug)]
+pub struct Paragraph<'a> {
+    name: &'a str,
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Paragraph<'a> {
+    pub fn lines(&self) -> impl Iterator<Item = Line<'a>> {
+        numbered_lines(self.name, self.text, self.number)
+    }
+
+    pub fn error(&self, message: impl Display) -> InputError {
+        InputError {
+            name: self.name.to_owned(),
+            line: self.number,
+            column: None,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl Deref for Paragraph<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.text
+    }
+}
@@ -0,0 +1,11 @@
+//! Pieces shared by every day's solution.
+
+#[cfg(feature = "bench")]
+pub mod bench;
+pub mod examples;
+mod input;
+mod logging;
+pub mod parse;
+
+pub use input::{Input, InputError, Line, Paragraph};
+pub use logging::init_logging;
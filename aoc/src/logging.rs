@@ -0,0 +1,57 @@
+//! Verbosity flags shared by every day. Answers are printed to stdout, while
+//! progress and debug output go through `log` to stderr, at a level picked on
+//! the command line:
+//!
+//! - `-q` only shows warnings and errors,
+//! - by default info events are shown too,
+//! - `-v` adds debug events,
+//! - `-vv` (or `-v -v`) adds trace events.
+
+use log::{LevelFilter, Log, Metadata, Record};
+
+/// Writes each event's message to stderr as is, so multi-line maps and
+/// tables come out the way they used to be printed.
+struct Stderr;
+
+impl Log for Stderr {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!("{}", record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: Stderr = Stderr;
+
+/// The level asked for by the `-q`, `-v` and `-vv` arguments.
+fn verbosity(args: impl IntoIterator<Item = String>) -> LevelFilter {
+    let mut verbose = 0;
+    let mut quiet = false;
+    for arg in args {
+        match arg.as_str() {
+            "-q" => quiet = true,
+            "-v" => verbose += 1,
+            "-vv" => verbose += 2,
+            _ => {}
+        }
+    }
+
+    match (quiet, verbose) {
+        (true, _) => LevelFilter::Warn,
+        (false, 0) => LevelFilter::Info,
+        (false, 1) => LevelFilter::Debug,
+        (false, _) => LevelFilter::Trace,
+    }
+}
+
+/// Sends log events to stderr at the level given on the command line.
+pub fn init_logging() {
+    log::set_logger(&LOGGER).expect("logging is only set up once");
+    log::set_max_level(verbosity(std::env::args().skip(1)));
+}
@@ -0,0 +1,221 @@
+//! A handful of parser combinators for line based puzzle input.
+//!
+//! A parser is any `Fn(&str) -> PResult<T>` that consumes some of the front
+//! of its input and returns what's left along with the value it read. Run one
+//! over a whole line with `Line::parse_with` to get an `InputError` that
+//! points at the line and column where it went wrong.
+
+use std::{fmt::Display, str::FromStr};
+
+/// What a parser was looking for when it failed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Expected {
+    Tag(&'static str),
+    Number,
+    Word,
+    Char(&'static str),
+    End,
+}
+
+impl Display for Expected {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expected::Tag(tag) => write!(f, "expected {tag:?}"),
+            Expected::Number => write!(f, "expected a number"),
+            Expected::Word => write!(f, "expected a word"),
+            Expected::Char(what) => write!(f, "expected {what}"),
+            Expected::End => write!(f, "expected the end of the line"),
+        }
+    }
+}
+
+/// A failed parse: what was expected, and how much input was left unread at
+/// the point it was expected, which `Line` turns back into a column.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    pub expected: Expected,
+    pub remaining: usize,
+}
+
+impl ParseError {
+    fn new(expected: Expected, input: &str) -> ParseError {
+        ParseError {
+            expected,
+            remaining: input.len(),
+        }
+    }
+}
+
+pub type PResult<'a, T> = Result<(&'a str, T), ParseError>;
+
+/// Exactly the given text.
+pub fn tag<'a>(tag: &'static str) -> impl Fn(&'a str) -> PResult<'a, &'a str> {
+    move |input: &'a str| match input.strip_prefix(tag) {
+        Some(rest) => Ok((rest, &input[..tag.len()])),
+        None => Err(ParseError::new(Expected::Tag(tag), input)),
+    }
+}
+
+/// A decimal number with an optional leading `-`.
+pub fn number<'a, T: FromStr>() -> impl Fn(&'a str) -> PResult<'a, T> {
+    move |input: &'a str| {
+        let sign = usize::from(input.starts_with('-'));
+        let digits = input[sign..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(input.len() - sign);
+        input[..sign + digits]
+            .parse()
+            .map(|n| (&input[sign + digits..], n))
+            .map_err(|_| ParseError::new(Expected::Number, input))
+    }
+}
+
+/// One or more characters up to the next whitespace.
+pub fn word<'a>() -> impl Fn(&'a str) -> PResult<'a, &'a str> {
+    move |input: &'a str| {
+        let len = input.find(char::is_whitespace).unwrap_or(input.len());
+        if len == 0 {
+            return Err(ParseError::new(Expected::Word, input));
+        }
+        Ok((&input[len..], &input[..len]))
+    }
+}
+
+/// A single character matching `pred`, described by `what` on failure.
+pub fn char_if<'a>(
+    what: &'static str,
+    pred: impl Fn(char) -> bool,
+) -> impl Fn(&'a str) -> PResult<'a, char> {
+    move |input: &'a str| match input.chars().next() {
+        Some(c) if pred(c) => Ok((&input[c.len_utf8()..], c)),
+        _ => Err(ParseError::new(Expected::Char(what), input)),
+    }
+}
+
+/// Everything that's left, which may be nothing.
+pub fn rest<'a>() -> impl Fn(&'a str) -> PResult<'a, &'a str> {
+    move |input: &'a str| Ok(("", input))
+}
+
+/// Succeeds only when there's nothing left.
+pub fn end<'a>() -> impl Fn(&'a str) -> PResult<'a, ()> {
+    move |input: &'a str| {
+        if input.is_empty() {
+            Ok((input, ()))
+        } else {
+            Err(ParseError::new(Expected::End, input))
+        }
+    }
+}
+
+pub fn map<'a, A, B>(
+    parser: impl Fn(&'a str) -> PResult<'a, A>,
+    f: impl Fn(A) -> B,
+) -> impl Fn(&'a str) -> PResult<'a, B> {
+    move |input: &'a str| parser(input).map(|(rest, a)| (rest, f(a)))
+}
+
+/// `parser`, or `None` without consuming anything if it fails.
+pub fn opt<'a, T>(
+    parser: impl Fn(&'a str) -> PResult<'a, T>,
+) -> impl Fn(&'a str) -> PResult<'a, Option<T>> {
+    move |input: &'a str| match parser(input) {
+        Ok((rest, value)) => Ok((rest, Some(value))),
+        Err(_) => Ok((input, None)),
+    }
+}
+
+pub fn pair<'a, A, B>(
+    first: impl Fn(&'a str) -> PResult<'a, A>,
+    second: impl Fn(&'a str) -> PResult<'a, B>,
+) -> impl Fn(&'a str) -> PResult<'a, (A, B)> {
+    move |input: &'a str| {
+        let (input, a) = first(input)?;
+        let (input, b) = second(input)?;
+        Ok((input, (a, b)))
+    }
+}
+
+/// `second`, after skipping over `first`.
+pub fn preceded<'a, A, B>(
+    first: impl Fn(&'a str) -> PResult<'a, A>,
+    second: impl Fn(&'a str) -> PResult<'a, B>,
+) -> impl Fn(&'a str) -> PResult<'a, B> {
+    map(pair(first, second), |(_, b)| b)
+}
+
+/// `first`, then skipping over `second`.
+pub fn terminated<'a, A, B>(
+    first: impl Fn(&'a str) -> PResult<'a, A>,
+    second: impl Fn(&'a str) -> PResult<'a, B>,
+) -> impl Fn(&'a str) -> PResult<'a, A> {
+    map(pair(first, second), |(a, _)| a)
+}
+
+/// `inner`, between `open` and `close`.
+pub fn delimited<'a, A, B, C>(
+    open: impl Fn(&'a str) -> PResult<'a, A>,
+    inner: impl Fn(&'a str) -> PResult<'a, B>,
+    close: impl Fn(&'a str) -> PResult<'a, C>,
+) -> impl Fn(&'a str) -> PResult<'a, B> {
+    preceded(open, terminated(inner, close))
+}
+
+/// One or more `item`s with a `separator` between each.
+pub fn separated<'a, T, S>(
+    item: impl Fn(&'a str) -> PResult<'a, T>,
+    separator: impl Fn(&'a str) -> PResult<'a, S>,
+) -> impl Fn(&'a str) -> PResult<'a, Vec<T>> {
+    move |input: &'a str| {
+        let (mut input, first) = item(input)?;
+        let mut items = vec![first];
+        while let Ok((rest, _)) = separator(input) {
+            let (rest, next) = item(rest)?;
+            items.push(next);
+            input = rest;
+        }
+        Ok((input, items))
+    }
+}
+
+/// A set of parsers to try in order, as taken by `alt`.
+pub trait Choice<'a, T> {
+    fn choose(&self, input: &'a str) -> PResult<'a, T>;
+}
+
+macro_rules! impl_choice {
+    ($($parser:ident),+) => {
+        impl<'a, T, $($parser),+> Choice<'a, T> for ($($parser,)+)
+        where
+            $($parser: Fn(&'a str) -> PResult<'a, T>),+
+        {
+            #[allow(non_snake_case)]
+            fn choose(&self, input: &'a str) -> PResult<'a, T> {
+                let ($($parser,)+) = self;
+                let mut furthest: Option<ParseError> = None;
+                $(
+                    match $parser(input) {
+                        Ok(parsed) => return Ok(parsed),
+                        Err(e) => {
+                            if furthest.as_ref().map_or(true, |f| e.remaining < f.remaining) {
+                                furthest = Some(e);
+                            }
+                        }
+                    }
+                )+
+                Err(furthest.unwrap())
+            }
+        }
+    };
+}
+
+impl_choice!(A, B);
+impl_choice!(A, B, C);
+impl_choice!(A, B, C, D);
+impl_choice!(A, B, C, D, E);
+
+/// The first of the parsers that succeeds. If none do, the error from the
+/// one that got furthest is reported.
+pub fn alt<'a, T>(choices: impl Choice<'a, T>) -> impl Fn(&'a str) -> PResult<'a, T> {
+    move |input: &'a str| choices.choose(input)
+}
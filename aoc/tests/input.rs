@@ -0,0 +1,108 @@
+use aoc::{
+    parse::{number, pair, tag, terminated},
+    Input,
+};
+
+#[test]
+fn lines_are_normalized_and_numbered() {
+    let input = Input::new("in.txt", "a  \r\nb\n\n\nc\n\n\n");
+    assert_eq!(input.text(), "a\nb\n\n\nc\n");
+    let lines: Vec<_> = input.lines().map(|line| (line.number, line.text)).collect();
+    assert_eq!(lines, [(1, "a"), (2, "b"), (3, ""), (4, ""), (5, "c")]);
+}
+
+#[test]
+fn paragraphs_keep_their_line_numbers() {
+    let input = Input::new("in.txt", "\n1\n2\n\n\n3\n");
+    let paragraphs: Vec<_> = input
+        .paragraphs()
+        .map(|paragraph| (paragraph.number, paragraph.text))
+        .collect();
+    assert_eq!(paragraphs, [(2, "1\n2"), (6, "3")]);
+
+    let numbers: Vec<_> = input
+        .paragraphs()
+        .flat_map(|paragraph| paragraph.lines().map(|line| line.number))
+        .collect();
+    assert_eq!(numbers, [2, 3, 6]);
+}
+
+#[test]
+fn parse_with_returns_the_value_of_a_whole_line() {
+    let input = Input::new("in.txt", "2-4\n");
+    let line = input.lines().next().unwrap();
+    let range = line.parse_with(pair(terminated(number::<u32>(), tag("-")), number::<u32>()));
+    assert_eq!(range.unwrap(), (2, 4));
+}
+
+#[test]
+fn parse_with_reports_the_column_it_failed_at() {
+    let input = Input::new("in.txt", "2-4\n2-x\n");
+    let line = input.lines().nth(1).unwrap();
+    let e = line
+        .parse_with(pair(terminated(number::<u32>(), tag("-")), number::<u32>()))
+        .unwrap_err();
+    assert_eq!((e.line, e.column), (2, Some(3)));
+    assert_eq!(e.message, "expected a number, found \"x\"");
+    assert_eq!(e.to_string(), "in.txt:2:3: expected a number, found \"x\"");
+}
+
+#[test]
+fn parse_with_reports_leftovers_and_running_out() {
+    let input = Input::new("in.txt", "12 apples\n12\n");
+    let mut lines = input.lines();
+
+    let e = lines
+        .next()
+        .unwrap()
+        .parse_with(number::<u32>())
+        .unwrap_err();
+    assert_eq!((e.line, e.column), (1, Some(3)));
+    assert_eq!(e.message, "expected the end of the line, found \" apples\"");
+
+    let e = lines
+        .next()
+        .unwrap()
+        .parse_with(terminated(number::<u32>(), tag(" apples")))
+        .unwrap_err();
+    assert_eq!((e.line, e.column), (2, Some(3)));
+    assert_eq!(e.message, "expected \" apples\", found the end of the line");
+}
+
+#[test]
+fn parse_reports_the_line_without_a_column() {
+    let input = Input::new("in.txt", "7\nseven\n");
+    let lines: Vec<_> = input.lines().collect();
+    assert_eq!(lines[0].parse::<i32>().unwrap(), 7);
+
+    let e = lines[1].parse::<i32>().unwrap_err();
+    assert_eq!((e.line, e.column), (2, None));
+    assert_eq!(
+        e.to_string(),
+        "in.txt:2: invalid digit found in string: \"seven\""
+    );
+}
+
+fn args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|arg| arg.to_string()).collect()
+}
+
+#[test]
+fn path_is_the_argument_that_isnt_a_flag() {
+    let path = |list: &[&str]| Input::path_from_args(&args(list)).unwrap().to_owned();
+    assert_eq!(path(&[]), "input.txt");
+    assert_eq!(path(&["-v", "--dot"]), "input.txt");
+    assert_eq!(path(&["in.txt"]), "in.txt");
+    assert_eq!(path(&["-vv", "--jobs=4", "in.txt", "--schedule"]), "in.txt");
+    assert_eq!(path(&["-q", "-"]), "-");
+}
+
+#[test]
+fn option_values_need_an_equals_sign() {
+    let e = Input::path_from_args(&args(&["--jobs", "4", "in.txt"])).unwrap_err();
+    assert_eq!(e.kind(), std::io::ErrorKind::InvalidInput);
+    assert_eq!(
+        e.to_string(),
+        "expected one input path, found '4' and 'in.txt' (options take their value as --name=value)"
+    );
+}
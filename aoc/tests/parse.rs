@@ -0,0 +1,113 @@
+use aoc::parse::{
+    alt, char_if, delimited, end, map, number, opt, pair, preceded, rest, separated, tag,
+    terminated, word, Expected, PResult, ParseError,
+};
+
+fn error(expected: Expected, remaining: &str) -> ParseError {
+    ParseError {
+        expected,
+        remaining: remaining.len(),
+    }
+}
+
+#[test]
+fn tag_matches_exact_text() {
+    assert_eq!(tag("move ")("move 3"), Ok(("3", "move ")));
+    assert_eq!(
+        tag("move ")("mov 3"),
+        Err(error(Expected::Tag("move "), "mov 3"))
+    );
+}
+
+#[test]
+fn number_reads_digits_and_a_sign() {
+    assert_eq!(number::<i32>()("42 from"), Ok((" from", 42)));
+    assert_eq!(number::<i64>()("-17,3"), Ok((",3", -17)));
+    assert_eq!(number::<u8>()("7"), Ok(("", 7)));
+}
+
+#[test]
+fn number_fails_without_moving() {
+    assert_eq!(number::<i32>()("x1"), Err(error(Expected::Number, "x1")));
+    assert_eq!(number::<i32>()("-"), Err(error(Expected::Number, "-")));
+    assert_eq!(number::<u32>()("-5"), Err(error(Expected::Number, "-5")));
+    assert_eq!(number::<u8>()("300"), Err(error(Expected::Number, "300")));
+}
+
+#[test]
+fn word_stops_at_whitespace() {
+    assert_eq!(word()("a.txt 12"), Ok((" 12", "a.txt")));
+    assert_eq!(word()("abc"), Ok(("", "abc")));
+    assert_eq!(word()(" abc"), Err(error(Expected::Word, " abc")));
+    assert_eq!(word()(""), Err(error(Expected::Word, "")));
+}
+
+#[test]
+fn char_if_takes_one_matching_char() {
+    let upper = char_if("a capital", |c| c.is_ascii_uppercase());
+    assert_eq!(upper("Ab"), Ok(("b", 'A')));
+    assert_eq!(upper("ab"), Err(error(Expected::Char("a capital"), "ab")));
+    assert_eq!(upper(""), Err(error(Expected::Char("a capital"), "")));
+}
+
+#[test]
+fn rest_and_end() {
+    assert_eq!(rest()("all of it"), Ok(("", "all of it")));
+    assert_eq!(end()(""), Ok(("", ())));
+    assert_eq!(end()("more"), Err(error(Expected::End, "more")));
+}
+
+#[test]
+fn sequences_report_where_they_failed() {
+    let range = pair(terminated(number::<u32>(), tag("-")), number::<u32>());
+    assert_eq!(range("2-4,6-8"), Ok((",6-8", (2, 4))));
+    assert_eq!(range("2-x"), Err(error(Expected::Number, "x")));
+    assert_eq!(range("2+4"), Err(error(Expected::Tag("-"), "+4")));
+
+    let crate_ = delimited(
+        tag("["),
+        char_if("a crate", |c| c.is_ascii_uppercase()),
+        tag("]"),
+    );
+    assert_eq!(crate_("[Z] [M]"), Ok((" [M]", 'Z')));
+    assert_eq!(crate_("[Z [M]"), Err(error(Expected::Tag("]"), " [M]")));
+    assert_eq!(preceded(tag("$ cd "), word())("$ cd /"), Ok(("", "/")));
+}
+
+#[test]
+fn map_and_opt() {
+    let doubled = map(number::<i32>(), |n| n * 2);
+    assert_eq!(doubled("21!"), Ok(("!", 42)));
+    assert_eq!(opt(tag("-"))("-1"), Ok(("1", Some("-"))));
+    assert_eq!(opt(tag("-"))("1"), Ok(("1", None)));
+}
+
+#[test]
+fn separated_needs_an_item_after_each_separator() {
+    let items = separated(number::<i32>(), tag(", "));
+    assert_eq!(items("79, 98 end"), Ok((" end", vec![79, 98])));
+    assert_eq!(items("5"), Ok(("", vec![5])));
+    assert_eq!(items("79, x"), Err(error(Expected::Number, "x")));
+    assert_eq!(items(""), Err(error(Expected::Number, "")));
+}
+
+#[test]
+fn alt_takes_the_first_success_or_the_furthest_failure() {
+    fn op(input: &str) -> PResult<'_, &str> {
+        alt((
+            preceded(tag("old * "), word()),
+            preceded(tag("old + "), word()),
+            tag("old * old"),
+        ))(input)
+    }
+    assert_eq!(op("old + 3"), Ok(("", "3")));
+    assert_eq!(op("old * old"), Ok(("", "old")));
+    assert_eq!(
+        op("old - 3"),
+        Err(error(Expected::Tag("old * "), "old - 3"))
+    );
+
+    let choice = alt((pair(tag("a"), tag("b")), pair(tag("a"), tag("c"))));
+    assert_eq!(choice("ad"), Err(error(Expected::Tag("b"), "d")));
+    assert_eq!(choice("x"), Err(error(Expected::Tag("a"), "x")));
+}
@@ -0,0 +1,31 @@
+#!/bin/sh
+# Benchmarks parse, part one and part two of every day that has benches, or
+# just the days given before `--`. Anything after `--` goes to criterion, so
+#
+#   ./bench.sh -- --save-baseline before
+#   ./bench.sh 16 19 -- --baseline before
+#
+# saves a baseline for every day and then compares days 16 and 19 against it.
+# Reports end up in each day's target/criterion directory.
+set -e
+
+cd "$(dirname "$0")"
+
+days=""
+while [ $# -gt 0 ] && [ "$1" != "--" ]; do
+    days="$days $1"
+    shift
+done
+[ "$1" = "--" ] && shift
+
+for bench in [0-9][0-9]_*/benches/parts.rs; do
+    dir=${bench%%/*}
+    if [ -n "$days" ]; then
+        case " $days " in
+            *" ${dir%%_*} "* | *" $dir "*) ;;
+            *) continue ;;
+        esac
+    fi
+    echo "== $dir"
+    (cd "$dir" && cargo bench --bench parts -- "$@")
+done